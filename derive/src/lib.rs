@@ -1,4 +1,10 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
 
 use heck::{CamelCase, SnakeCase};
 use ldtk2::{
@@ -8,12 +14,53 @@ use proc_macro::TokenStream as TStream;
 use proc_macro2::TokenStream;
 use proc_macro_error::{abort, emit_call_site_error, proc_macro_error};
 use quote::{format_ident, quote};
-use syn::{parse::Parse, parse_macro_input, Ident, LitStr, Token, Visibility};
+use syn::{
+    bracketed, parenthesized, parse::Parse, parse_macro_input, punctuated::Punctuated, Ident, Lit,
+    LitStr, Token, Visibility,
+};
 
 struct LdtkDeclaration {
     vis: Visibility,
     name: Ident,
     path: LitStr,
+    skip_layers: Vec<String>,
+    skip_entities: Vec<String>,
+    pixels_per_unit: f32,
+    /// The name of the `World` alias, `None` if it should not be generated
+    project_alias: Option<Ident>,
+    /// The type `Color` fields are parsed into
+    color_type: syn::Type,
+    /// Whether the items are wrapped in `mod #name`
+    wrap_module: bool,
+}
+
+fn parse_string_list(input: syn::parse::ParseStream) -> syn::Result<Vec<String>> {
+    let list;
+    bracketed!(list in input);
+
+    Ok(Punctuated::<LitStr, Token!(,)>::parse_terminated(&list)?
+        .into_iter()
+        .map(|value| value.value())
+        .collect())
+}
+
+/// Parses the path, either as a literal or as `env!("VAR")` which is turned into `"${VAR}"`
+fn parse_path(input: syn::parse::ParseStream) -> syn::Result<LitStr> {
+    if input.peek(Ident) && input.peek2(Token!(!)) {
+        let mac: Ident = input.parse()?;
+        if mac != "env" {
+            return Err(syn::Error::new(mac.span(), "Expected a path or `env!`"));
+        }
+        input.parse::<Token!(!)>()?;
+
+        let content;
+        parenthesized!(content in input);
+        let var: LitStr = content.parse()?;
+
+        return Ok(LitStr::new(&format!("${{{}}}", var.value()), var.span()));
+    }
+
+    input.parse()
 }
 
 impl Parse for LdtkDeclaration {
@@ -21,73 +68,334 @@ impl Parse for LdtkDeclaration {
         let vis: Visibility = input.parse()?;
         let name: Ident = input.parse()?;
         input.parse::<Token!(,)>()?;
-        let path: LitStr = input.parse()?;
+        let path = parse_path(input)?;
+
+        let mut skip_layers = vec![];
+        let mut skip_entities = vec![];
+        let mut pixels_per_unit = 1.0;
+        let mut project_alias = Some(format_ident!("Project"));
+        let mut color_type: syn::Type = syn::parse_quote!(::bevy::render::color::Color);
+        let mut wrap_module = true;
+
+        while input.parse::<Option<Token!(,)>>()?.is_some() && !input.is_empty() {
+            let option: Ident = input.parse()?;
+
+            let content;
+            parenthesized!(content in input);
+
+            match option.to_string().as_str() {
+                "skip_layers" => skip_layers.extend(parse_string_list(&content)?),
+                "skip_entities" => skip_entities.extend(parse_string_list(&content)?),
+                "pixels_per_unit" => {
+                    pixels_per_unit = match content.parse()? {
+                        Lit::Int(int) => int.base10_parse()?,
+                        Lit::Float(float) => float.base10_parse()?,
+                        lit => return Err(syn::Error::new(lit.span(), "Expected a number")),
+                    }
+                }
+                "project_alias" => {
+                    let alias: Ident = content.parse()?;
+                    project_alias = if alias == "none" { None } else { Some(alias) };
+                }
+                "color_type" => color_type = content.parse()?,
+                "module" => {
+                    let module: Ident = content.parse()?;
+                    if module != "none" {
+                        return Err(syn::Error::new(module.span(), "Expected `none`"));
+                    }
+                    wrap_module = false;
+                }
+                _ => return Err(syn::Error::new(option.span(), "Unknown option")),
+            }
+        }
 
-        Ok(LdtkDeclaration { vis, name, path })
+        Ok(LdtkDeclaration {
+            vis,
+            name,
+            path,
+            skip_layers,
+            skip_entities,
+            pixels_per_unit,
+            project_alias,
+            color_type,
+            wrap_module,
+        })
     }
 }
 
+/// Generates a module with types for the given LDTK project
+///
+/// ```ignore
+/// ldtk!(pub levels, "assets/levels.ldtk", skip_layers(["Helper"]), skip_entities(["Marker"]));
+/// ```
+///
+/// Layers and entities listed in `skip_layers` and `skip_entities` are left out of the generated
+/// types. `pixels_per_unit(16)` sets how many pixels make up one unit in the transforms of
+/// entities and tiles, it defaults to `1`. `project_alias(MyWorld)` renames the generated `Project` alias
+/// of the world type, and `project_alias(none)` leaves it out. `color_type(my::Color)` parses
+/// `Color` fields into any type implementing `LdtkColor` instead of Bevy's `Color`.
+/// `module(none)` puts the items directly into the calling module instead of a new one, the
+/// visibility and name are then ignored.
+///
+/// The path may contain environment variables like `"${OUT_DIR}/levels.ldtk"`, or be given as
+/// `env!("LEVELS_LDTK")` to read it from a single variable. Relative paths start at the crate root.
+/// The generated `FILEPATH` is the resolved path, so it can be opened from any working directory.
+///
+/// Projects are read with the LDTK 0.9 schema. Multiple worlds, entity iids, `Tile` and `EntityRef`
+/// fields only exist from LDTK 1.0 on and are not supported, such fields are a compile error.
+///
+/// Multiline text fields are plain `String`s, arrays of them become `Vec<String>`.
+///
+/// `Int`, `Float` and `Bool` values that were exported as strings like `"5"` are read as what they
+/// spell, unless the `strict_fields` feature is enabled, which makes them an error.
+///
+/// Enum fields that may be unset are `Option`s which are `None` when the value is `null` or
+/// missing. A value meaning "none" in the LDTK enum is kept as a regular variant, prefer making the
+/// field nullable instead.
 #[proc_macro]
 #[proc_macro_error]
 pub fn ldtk(input: TStream) -> TStream {
-    let LdtkDeclaration { vis, name, path } = parse_macro_input!(input as LdtkDeclaration);
-
-    let ldtk = match Ldtk::from_path(path.value()) {
-        Ok(ldtk) => ldtk,
-        Err(err) => abort!(path, err),
-    };
+    let LdtkDeclaration {
+        vis,
+        name,
+        path,
+        skip_layers,
+        skip_entities,
+        pixels_per_unit,
+        project_alias,
+        color_type,
+        wrap_module,
+    } = parse_macro_input!(input as LdtkDeclaration);
+
+    let resolved_path = resolve_path(&path);
+    let filepath = resolved_path.to_string_lossy();
+    let project_dir = resolved_path
+        .parent()
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let ldtk = load_ldtk(&path, &resolved_path);
 
     let custom_enums = define_enums(&ldtk.defs.enums);
 
-    let entities = define_entities(&ldtk.defs.entities);
+    let entities = define_entities(&ldtk.defs.entities, &skip_entities);
+
+    let levels = define_levels(&ldtk.defs.level_fields, &ldtk.defs.layers, &skip_layers);
 
-    let levels = define_levels(&ldtk.defs.level_fields, &ldtk.defs.layers);
+    let aseprite_tilesets = define_aseprite_tilesets(&resolved_path, &ldtk.defs.tilesets);
 
-    let aseprite_tilesets = define_aseprite_tilesets(&path.value(), &ldtk.defs.tilesets);
+    let defaults = define_defaults(&ldtk);
 
     let uuid_bytes = uuid::Uuid::new_v4().as_bytes().to_vec();
 
+    let json_version = &ldtk.json_version;
+
+    let project_alias = project_alias.map(|alias| {
+        quote! {
+            pub type #alias = LdtkWorld;
+        }
+    });
 
-    let expanded = quote! {
-        #vis mod #name {
+    let items = quote! {
+        /// The type `Color` fields are parsed into
+        pub type FieldColor = #color_type;
 
-            pub mod enums {
-                #custom_enums
-            }
+        pub mod enums {
+            #custom_enums
+        }
 
-            #entities
+        #entities
 
-            #levels
+        #levels
 
-            pub const FILEPATH: &'static str = #path;
+        /// The path the project was read from, resolved against the crate root
+        pub const FILEPATH: &'static str = #filepath;
 
-            pub mod aseprite_tilesets {
-                #aseprite_tilesets
-            }
+        /// The directory of `FILEPATH`, which `FilePath` fields are relative to
+        pub const PROJECT_DIR: &'static str = #project_dir;
 
-            #[derive(Debug, Default, Clone)]
-            pub struct WorldType;
+        /// How many pixels make up one unit in generated transforms
+        pub const PIXELS_PER_UNIT: f32 = #pixels_per_unit;
 
-            impl ::bevy_spicy_ldtk::private::TypeUuid for WorldType {
-                const TYPE_UUID: ::bevy_spicy_ldtk::private::Uuid = ::bevy_spicy_ldtk::private::Uuid::from_bytes([#(#uuid_bytes),*]);
-            }
+        /// The transform of `tile` within its layer, scaled by `PIXELS_PER_UNIT`
+        pub fn tile_transform(tile: &::bevy_spicy_ldtk::Tile) -> ::bevy::transform::components::Transform {
+            let position = tile.position_px.as_f32() / PIXELS_PER_UNIT;
 
-            pub type Project = ::bevy_spicy_ldtk::World<
-                WorldType,
-                LevelFields,
-                ProjectEntities,
-                Layers
-            >;
+            ::bevy::transform::components::Transform::from_xyz(position.x, position.y, 0.0)
         }
+
+        /// The LDTK version of the project these types were generated from
+        pub const LDTK_JSON_VERSION: &'static str = #json_version;
+
+        #defaults
+
+        pub mod aseprite_tilesets {
+            #aseprite_tilesets
+        }
+
+        #[derive(Debug, Default, Clone)]
+        pub struct WorldType;
+
+        impl ::bevy_spicy_ldtk::private::TypeUuid for WorldType {
+            const TYPE_UUID: ::bevy_spicy_ldtk::private::Uuid = ::bevy_spicy_ldtk::private::Uuid::from_bytes([#(#uuid_bytes),*]);
+        }
+
+        type LdtkWorld = ::bevy_spicy_ldtk::World<
+            WorldType,
+            LevelFields,
+            ProjectEntities,
+            Layers
+        >;
+
+        #project_alias
+    };
+
+    let expanded = if wrap_module {
+        quote! {
+            #vis mod #name {
+                #items
+            }
+        }
+    } else {
+        items
     };
 
     expanded.into()
 }
 
-fn define_aseprite_tilesets(path: &str, tilesets: &[TilesetDefinition]) -> TokenStream {
+/// The LDTK versions whose schema `ldtk2` understands
+const SUPPORTED_LDTK_VERSIONS: &str = "0.9.x";
+
+fn is_supported_version(version: &str) -> bool {
+    version.starts_with("0.9.")
+}
+
+/// Reads only the `jsonVersion` of a project, so that it is available even if parsing fails
+fn read_json_version(path: &Path) -> Option<String> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&text).ok()?;
+
+    json.get("jsonVersion")?.as_str().map(str::to_string)
+}
+
+thread_local! {
+    /// Parsed projects keyed by their canonical path and modification time
+    static LDTK_CACHE: RefCell<HashMap<(PathBuf, Option<SystemTime>), Rc<Ldtk>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Parses the project at `resolved_path`, reusing earlier parses of the same unchanged file
+fn load_ldtk(path: &LitStr, resolved_path: &Path) -> Rc<Ldtk> {
+    let canonical_path = resolved_path
+        .canonicalize()
+        .unwrap_or_else(|_| resolved_path.to_path_buf());
+    let modified = std::fs::metadata(&canonical_path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    let key = (canonical_path, modified);
+
+    if let Some(ldtk) = LDTK_CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return ldtk;
+    }
+
+    let ldtk = match Ldtk::from_path(&key.0) {
+        Ok(ldtk) => Rc::new(ldtk),
+        Err(err) => match read_json_version(&key.0) {
+            Some(version) if !is_supported_version(&version) => abort!(
+                path,
+                "The project was saved by LDTK {}, but only {} is supported: {}",
+                version,
+                SUPPORTED_LDTK_VERSIONS,
+                err
+            ),
+            _ => abort!(path, err),
+        },
+    };
+
+    LDTK_CACHE.with(|cache| cache.borrow_mut().insert(key, ldtk.clone()));
+
+    ldtk
+}
+
+/// Resolves the given path the same way `include_str!` would
+///
+/// Environment variables written as `$VAR` or `${VAR}` are interpolated first. Relative paths are
+/// then resolved against `CARGO_MANIFEST_DIR`, while absolute paths are passed through unchanged.
+fn resolve_path(path: &LitStr) -> PathBuf {
+    let value = path.value();
+    let mut interpolated = String::with_capacity(value.len());
+    let mut rest = value.as_str();
+
+    while let Some(start) = rest.find('$') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let (var, remaining) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => abort!(path, "Unclosed `${` in path"),
+            }
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+
+        match std::env::var(var) {
+            Ok(val) => interpolated.push_str(&val),
+            Err(err) => abort!(
+                path,
+                "Could not read environment variable `{}`: {}",
+                var,
+                err
+            ),
+        }
+
+        rest = remaining;
+    }
+    interpolated.push_str(rest);
+
+    let resolved = PathBuf::from(interpolated);
+
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) if resolved.is_relative() => PathBuf::from(manifest_dir).join(resolved),
+        _ => resolved,
+    }
+}
+
+fn define_defaults(ldtk: &Ldtk) -> TokenStream {
+    let pivot_x = ldtk.default_pivot_x as f32;
+    let pivot_y = 1.0 - ldtk.default_pivot_y as f32;
+    let grid_size = ldtk.default_grid_size;
+    let level_width = ldtk.default_level_width;
+    let level_height = ldtk.default_level_height;
+
+    quote! {
+        /// The defaults the project uses for new entities and levels
+        pub struct Defaults;
+
+        impl Defaults {
+            pub const PIVOT_X: f32 = #pivot_x;
+            /// The default pivot on the y axis, y up
+            pub const PIVOT_Y: f32 = #pivot_y;
+            pub const GRID_SIZE: i64 = #grid_size;
+            pub const LEVEL_WIDTH_PX: i64 = #level_width;
+            pub const LEVEL_HEIGHT_PX: i64 = #level_height;
+        }
+    }
+}
+
+#[cfg(not(feature = "aseprite"))]
+fn define_aseprite_tilesets(_path: &Path, _tilesets: &[TilesetDefinition]) -> TokenStream {
+    quote! {}
+}
+
+#[cfg(feature = "aseprite")]
+fn define_aseprite_tilesets(path: &Path, tilesets: &[TilesetDefinition]) -> TokenStream {
     let tilesets = tilesets.iter().map(|def| {
         if def.rel_path.ends_with(".aseprite") || def.rel_path.ends_with(".ase") {
-            let mut path = PathBuf::from_str(path).unwrap();
+            let mut path = path.to_path_buf();
             path.pop();
             path.push(&def.rel_path);
 
@@ -111,7 +419,13 @@ fn define_aseprite_tilesets(path: &str, tilesets: &[TilesetDefinition]) -> Token
 fn define_levels(
     level_fields: &[FieldDefinition],
     level_layers: &[LayerDefinition],
+    skip_layers: &[String],
 ) -> TokenStream {
+    let level_layers = level_layers
+        .iter()
+        .filter(|def| !skip_layers.contains(&def.identifier))
+        .collect::<Vec<_>>();
+
     let ref custom_idents = level_fields
         .iter()
         .map(|def| &def.identifier)
@@ -119,6 +433,35 @@ fn define_levels(
     let (ref custom_names, ref custom_types): (Vec<Ident>, Vec<TokenStream>) =
         define_fields(level_fields).into_iter().unzip();
 
+    let ref custom_parsers = level_fields.iter().map(field_parser).collect::<Vec<_>>();
+    let custom_validators = level_fields.iter().map(field_validator);
+    let missing_error =
+        quote! { return Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForLevels) };
+    // Missing fields inherit the default of their definition
+    let custom_missing = level_fields.iter().map(|def| {
+        let parser = field_parser(def);
+        match field_default(def) {
+            Some(default) => quote! {
+                #parser(&::bevy_spicy_ldtk::private::serde_json::from_str(#default)?)?
+            },
+            None => null_value(def).unwrap_or_else(|| missing_error.clone()),
+        }
+    });
+    // Explicit nulls are kept for nullable fields
+    let custom_null = level_fields.iter().map(|def| {
+        let parser = field_parser(def);
+        if let Some(null) = null_value(def) {
+            return null;
+        }
+        match field_default(def) {
+            Some(default) => quote! {
+                #parser(&::bevy_spicy_ldtk::private::serde_json::from_str(#default)?)?
+            },
+            None => missing_error.clone(),
+        }
+    });
+    let field_meta = level_fields.iter().map(define_field_meta);
+
     let layers = level_layers.iter().map(|def| {
         let ident = format_ident!("{}", def.identifier.to_snake_case());
 
@@ -131,6 +474,7 @@ fn define_levels(
         .iter()
         .map(|def| format_ident!("{}", def.identifier.to_snake_case()))
         .collect::<Vec<_>>();
+    let ref layer_uids = level_layers.iter().map(|def| def.uid).collect::<Vec<_>>();
     let ref layer_idents = level_layers
         .iter()
         .map(|def| &def.identifier)
@@ -139,29 +483,43 @@ fn define_levels(
     quote! {
         #[derive(Debug)]
         pub struct LevelFields {
-            #(pub #custom_names: #custom_types),*
+            #(pub #custom_names: #custom_types,)*
+            pub raw_editor_values: ::bevy_spicy_ldtk::RawEditorValues,
+        }
+
+        impl LevelFields {
+            pub const META: &'static [::bevy_spicy_ldtk::FieldMeta] = &[#(#field_meta),*];
+        }
+
+        impl ::bevy_spicy_ldtk::FieldAccess for LevelFields {
+            fn fields(&self) -> ::bevy::utils::HashMap<String, ::bevy_spicy_ldtk::FieldValue> {
+                #[allow(unused_mut)]
+                let mut fields = ::bevy::utils::HashMap::default();
+                #(fields.insert(#custom_idents.to_string(), ::bevy_spicy_ldtk::ToFieldValue::to_field_value(&self.#custom_names));)*
+                fields
+            }
         }
 
         impl ::bevy_spicy_ldtk::DeserializeLdtkFields for LevelFields {
             fn deserialize_ldtk(instances: &[::bevy_spicy_ldtk::private::ldtk2::FieldInstance]) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
-                #(let mut #custom_names: Option<#custom_types> = None;)*
-
                 #(
-                    #custom_names = instances.iter().find(|field| field.identifier == #custom_idents)
-                        .and_then(|field| field.value.as_ref())
-                        .map(|value| ::bevy_spicy_ldtk::private::parse_field(value))
-                        .transpose()?;
+                    let #custom_names: #custom_types = match instances.iter().find(|field| field.identifier == #custom_idents) {
+                        None => #custom_missing,
+                        Some(field) => match &field.value {
+                            None => #custom_null,
+                            Some(value) => {
+                                let value = #custom_parsers(value)?;
+                                #custom_validators
+                                value
+                            }
+                        },
+                    };
                 )*
 
-
-                match (#(#custom_names),*) {
-                    (#(Some(#custom_names)),*) => {
-                        Ok(LevelFields {
-                            #(#custom_names),*
-                        })
-                    }
-                    _ => Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForLevels)
-                }
+                Ok(LevelFields {
+                    #(#custom_names,)*
+                    raw_editor_values: ::bevy_spicy_ldtk::private::raw_editor_values(instances),
+                })
             }
         }
 
@@ -171,27 +529,58 @@ fn define_levels(
         }
 
 
+        impl Layers {
+            /// Iterates over all layers in the order they are defined in
+            pub fn iter(&self) -> impl Iterator<Item = &::bevy_spicy_ldtk::Layer<ProjectEntities>> {
+                vec![#(&self.#layer_names),*].into_iter()
+            }
+
+            /// Returns the layer with the given identifier as written in LDTK
+            pub fn get(&self, identifier: &str) -> Option<&::bevy_spicy_ldtk::Layer<ProjectEntities>> {
+                match identifier {
+                    #(#layer_idents => Some(&self.#layer_names),)*
+                    _ => None,
+                }
+            }
+
+            /// Iterates over the layers that are visible in the editor
+            pub fn visible(&self) -> impl Iterator<Item = &::bevy_spicy_ldtk::Layer<ProjectEntities>> {
+                self.iter().filter(|layer| layer.visible)
+            }
+
+            /// Iterates over the entities of every entity layer
+            pub fn entities(&self) -> impl Iterator<Item = &ProjectEntities> {
+                self.iter().filter_map(|layer| layer.as_entities())
+            }
+        }
+
         impl ::bevy_spicy_ldtk::DeserializeLDtkLayers for Layers {
             type Entities = ProjectEntities;
 
-            fn deserialize_ldtk(instances: &[::bevy_spicy_ldtk::private::ldtk2::LayerInstance]) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
-                #(let mut #layer_names: Option<_> = None;)*
+            fn layers(&self) -> Vec<&::bevy_spicy_ldtk::Layer<ProjectEntities>> {
+                self.iter().collect()
+            }
+
+            fn layer(&self, identifier: &str) -> Option<&::bevy_spicy_ldtk::Layer<ProjectEntities>> {
+                self.get(identifier)
+            }
 
+            fn deserialize_ldtk(instances: &[::bevy_spicy_ldtk::private::ldtk2::LayerInstance], level_top_left_px: ::bevy::math::IVec2) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
                 #(
-                    #layer_names = instances.iter().find(|layer| layer.identifier == #layer_idents)
-                        .map(|layer| ::bevy_spicy_ldtk::Layer::load(layer))
-                        .transpose()?;
+                    // Fall back to the definition uid in case the layer got renamed
+                    let #layer_names = instances.iter().position(|layer| layer.identifier == #layer_idents)
+                        .or_else(|| instances.iter().position(|layer| layer.layer_def_uid == #layer_uids))
+                        .ok_or_else(|| ::bevy_spicy_ldtk::error::LdtkError::MissingLayer(#layer_idents.to_string()))
+                        .and_then(|index| {
+                            // LDTK lists the top-most layer first
+                            let order = (instances.len() - 1 - index) as i32;
+                            ::bevy_spicy_ldtk::Layer::load(&instances[index], level_top_left_px, order)
+                        })?;
                 )*
 
-
-                match (#(#layer_names),*) {
-                    (#(Some(#layer_names)),*) => {
-                        Ok(Layers {
-                            #(#layer_names),*
-                        })
-                    }
-                    _ => Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForLayers)
-                }
+                Ok(Layers {
+                    #(#layer_names),*
+                })
             }
         }
     }
@@ -201,17 +590,38 @@ fn define_enums(enums: &[EnumDefinition]) -> TokenStream {
     let enums = enums.iter().map(|def| {
         let ident = format_ident!("{}", def.identifier.to_camel_case());
 
-        let fields = def.values.iter().map(|val| {
-            let field_ident = format_ident!("{}", val.id.to_camel_case());
-
-            quote! {#field_ident}
-        });
+        let ref fields = def
+            .values
+            .iter()
+            .map(|val| format_ident!("{}", val.id.to_camel_case()))
+            .collect::<Vec<_>>();
+        let ref names = def.values.iter().map(|val| &val.id).collect::<Vec<_>>();
+        let count = def.values.len();
+        let identifier = &def.identifier;
 
         quote! {
 
-            #[derive(Debug, ::bevy_spicy_ldtk::private::Deserialize)]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::bevy_spicy_ldtk::private::Deserialize)]
             pub enum #ident {
-                #(#fields),*
+                // LDTK values keep their original spelling
+                #(#[serde(rename = #names)] #fields),*
+            }
+
+            impl #ident {
+                /// The identifier of this enum as written in LDTK
+                pub const IDENTIFIER: &'static str = #identifier;
+                pub const ALL: &'static [#ident] = &[#(#ident::#fields),*];
+                pub const COUNT: usize = #count;
+            }
+
+            impl ::bevy_spicy_ldtk::ToFieldValue for #ident {
+                fn to_field_value(&self) -> ::bevy_spicy_ldtk::FieldValue {
+                    let name = match *self {
+                        #(#ident::#fields => #names),*
+                    };
+
+                    ::bevy_spicy_ldtk::FieldValue::Enum(name.to_string())
+                }
             }
         }
     });
@@ -221,22 +631,67 @@ fn define_enums(enums: &[EnumDefinition]) -> TokenStream {
     }
 }
 
-fn define_entities(ldtk_entities: &[EntityDefinition]) -> TokenStream {
+fn define_entities(ldtk_entities: &[EntityDefinition], skip_entities: &[String]) -> TokenStream {
+    let ldtk_entities = ldtk_entities
+        .iter()
+        .filter(|def| !skip_entities.contains(&def.identifier))
+        .collect::<Vec<_>>();
+
+    // Skipped entities are still accepted when loading, they are just not kept
+    let skipped_entities = if skip_entities.is_empty() {
+        quote! {}
+    } else {
+        quote! { #(#skip_entities)|* => {} }
+    };
+
     let entities = ldtk_entities.iter().map(|def| {
         let ident = format_ident!("{}", def.identifier.to_camel_case());
 
+        let identifier = &def.identifier;
+
         let custom_ident = format_ident!("{}Fields", def.identifier.to_camel_case());
 
-        let can_be_null = def.field_defs.iter().map(|def| def.can_be_null.clone());
-        let custom_default = def.field_defs.iter().map(|def| if def.can_be_null { quote! { None } } else { quote!{ unreachable!() }});
-        let custom_idents = def.field_defs.iter().map(|def| def.identifier.clone());
-        let (custom_names, custom_types): (Vec<Ident>, Vec<TokenStream>) =
+        let mut used_enums = def
+            .field_defs
+            .iter()
+            .filter_map(|field| {
+                let kind = field.field_definition_type.trim_start_matches("Array<").trim_end_matches('>');
+                kind.strip_prefix("LocalEnum.")
+            })
+            .map(|name| format!("[`enums::{0}`](enums::{0})", name.to_camel_case()))
+            .collect::<Vec<_>>();
+        used_enums.sort();
+        used_enums.dedup();
+        let uses_doc = if used_enums.is_empty() {
+            quote! {}
+        } else {
+            let doc = format!("Uses: {}", used_enums.join(", "));
+            quote! { #[doc = #doc] }
+        };
+
+        // Nullable fields are `None`, or empty arrays, both when missing and when explicitly set to null
+        let custom_default = def.field_defs.iter().map(|def| null_value(def).unwrap_or_else(|| quote!{ return Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForEntities) })).collect::<Vec<_>>();
+        let ref custom_idents = def.field_defs.iter().map(|def| def.identifier.clone()).collect::<Vec<_>>();
+        let ref custom_parsers = def.field_defs.iter().map(field_parser).collect::<Vec<_>>();
+        let custom_validators = def.field_defs.iter().map(field_validator);
+        let definition = define_entity_meta(def);
+        let (ref custom_names, ref custom_types): (Vec<Ident>, Vec<TokenStream>) =
             define_fields(&def.field_defs).into_iter().unzip();
 
         quote! {
             #[derive(Debug)]
             pub struct #custom_ident {
-                #(pub #custom_names: #custom_types),*
+                #(pub #custom_names: #custom_types,)*
+                pub raw_editor_values: ::bevy_spicy_ldtk::RawEditorValues,
+            }
+
+            impl ::bevy_spicy_ldtk::FieldAccess for #custom_ident {
+                fn fields(&self) -> ::bevy::utils::HashMap<String, ::bevy_spicy_ldtk::FieldValue> {
+                    #[allow(unused_mut)]
+                    let mut fields = ::bevy::utils::HashMap::default();
+                    #(fields.insert(#custom_idents.to_string(), ::bevy_spicy_ldtk::ToFieldValue::to_field_value(&self.#custom_names));)*
+                    fields
+                }
             }
 
             impl ::bevy_spicy_ldtk::DeserializeLdtkFields for #custom_ident {
@@ -244,55 +699,142 @@ fn define_entities(ldtk_entities: &[EntityDefinition]) -> TokenStream {
                     #(let #custom_names: #custom_types;)*
 
                     #(
-                        let tmp: Option<Result<Option<#custom_types>,_>> = instances.iter().find(|field| field.identifier == #custom_idents)
-                            .map(|field| field.value.as_ref().map(::bevy_spicy_ldtk::private::parse_field).transpose());
-
-                        #custom_names = match tmp {
-                            None => return Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForEntities),
-                            Some(Err(e)) => return Err(e),
-                            Some(Ok(val)) => if let Some(val) = val {
-                                val
-                            } else {
-                                if !#can_be_null {
-                                    return Err(::bevy_spicy_ldtk::error::LdtkError::MissingFieldsForEntities)
-                                } else {
-                                    #custom_default
+                        #custom_names = match instances.iter().find(|field| field.identifier == #custom_idents) {
+                            None => #custom_default,
+                            Some(field) => match &field.value {
+                                None => #custom_default,
+                                Some(value) => {
+                                    let value = #custom_parsers(value)?;
+                                    #custom_validators
+                                    value
                                 }
                             },
                         };
                     )*
                     Ok(#custom_ident {
                         #(#custom_names,)*
+                        raw_editor_values: ::bevy_spicy_ldtk::private::raw_editor_values(instances),
                     })
                 }
             }
 
+            #uses_doc
             #[derive(Debug)]
             pub struct #ident {
                 pub dimensions_px: ::bevy::math::IVec2,
+                /// The cell containing the pivot, y up
                 pub position_cell: ::bevy::math::IVec2,
+                /// The pixel at the pivot within the layer, y up
                 pub position_px: ::bevy::math::IVec2,
+                /// Where `position_px` lies within the entity, from `(0, 0)` at the bottom left to
+                /// `(1, 1)` at the top right
                 pub pivot: ::bevy::math::Vec2,
+                /// The grid size of the layer this entity was placed in
+                pub grid_size: i64,
+                /// The position in world space, y up
+                pub world_position_px: ::bevy::math::IVec2,
+                /// The draw order of the layer this entity was placed in
+                pub layer_order: i32,
+                /// The tile this entity is drawn with in the editor
+                pub tile: Option<::bevy_spicy_ldtk::TileRect>,
                 pub fields: #custom_ident,
             }
 
             impl #ident {
-                fn load(entity: &::bevy_spicy_ldtk::private::ldtk2::EntityInstance, parent_size_grid: ::bevy::math::IVec2, parent_size_px: ::bevy::math::IVec2) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
+                /// The identifier of this entity as written in LDTK
+                pub const IDENTIFIER: &'static str = #identifier;
+
+                pub fn definition() -> ::bevy_spicy_ldtk::EntityMeta {
+                    #definition
+                }
+
+                /// The bottom left corner within the layer, y up
+                ///
+                /// Unlike `position_px`, the corners lie on the edges between pixels.
+                pub fn bottom_left_px(&self) -> ::bevy::math::Vec2 {
+                    // `position_px` is the pixel row below the pivot, the pivot lies on its top edge
+                    let pivot_px = ::bevy::math::Vec2::new(self.position_px.x as f32, (self.position_px.y + 1) as f32);
+
+                    pivot_px - self.pivot * self.dimensions_px.as_f32()
+                }
+
+                /// The top left corner within the layer, y up
+                pub fn top_left_px(&self) -> ::bevy::math::Vec2 {
+                    self.bottom_left_px() + ::bevy::math::Vec2::new(0.0, self.dimensions_px.y as f32)
+                }
+
+                /// The center within the layer, y up
+                pub fn center_px(&self) -> ::bevy::math::Vec2 {
+                    self.bottom_left_px() + self.dimensions_px.as_f32() / 2.0
+                }
+
+                /// Whether this entity was resized away from the default size of its definition
+                pub fn is_resized(&self) -> bool {
+                    self.dimensions_px != Self::definition().dimensions_px
+                }
+
+                /// The transform of this entity in world space, scaled by `PIXELS_PER_UNIT`
+                ///
+                /// The z coordinate is the layer order, so entities of higher layers are drawn on top.
+                pub fn world_transform(&self) -> ::bevy::transform::components::Transform {
+                    let position = self.world_position_px.as_f32() / PIXELS_PER_UNIT;
+
+                    ::bevy::transform::components::Transform::from_xyz(position.x, position.y, self.layer_order as f32)
+                }
+
+                /// Returns the tileset of the tile this entity is drawn with
+                pub fn tileset<'w>(&self, world: &'w LdtkWorld) -> Option<&'w ::bevy_spicy_ldtk::Tileset> {
+                    self.tile.and_then(|tile| world.tilesets.get(&tile.tileset_uid))
+                }
+
+                /// Parses a single entity outside of loading a level
+                pub fn from_instance(entity: &::bevy_spicy_ldtk::private::ldtk2::EntityInstance, context: &::bevy_spicy_ldtk::EntityContext) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
+                    let result = if entity.identifier == Self::IDENTIFIER {
+                        Self::load(entity, context)
+                    } else {
+                        Err(::bevy_spicy_ldtk::error::LdtkError::UnknownEntityType(entity.identifier.clone()))
+                    };
+
+                    result.map_err(|source| ::bevy_spicy_ldtk::error::LdtkError::InvalidEntity {
+                        identifier: entity.identifier.clone(),
+                        source: Box::new(source),
+                    })
+                }
+
+                fn load(entity: &::bevy_spicy_ldtk::private::ldtk2::EntityInstance, context: &::bevy_spicy_ldtk::EntityContext) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
                     let dimensions_px = ::bevy::math::IVec2::new(entity.width as i32, entity.height as i32);
-                    let position_cell = ::bevy::math::IVec2::new(entity.grid[0] as i32, parent_size_grid.y - entity.grid[1] as i32 - 1);
+                    let position_cell = ::bevy::math::IVec2::new(entity.grid[0] as i32, context.parent_size_grid.y - entity.grid[1] as i32 - 1);
                     let pivot = ::bevy::math::Vec2::new(entity.pivot[0] as f32, 1.0 - entity.pivot[1] as f32);
-                    let position_px = ::bevy::math::IVec2::new(entity.px[0] as i32, parent_size_px.y - entity.px[1] as i32 - 1);
+                    let position_px = ::bevy::math::IVec2::new(entity.px[0] as i32, context.parent_size_px.y - entity.px[1] as i32 - 1);
+                    let grid_size = context.grid_size;
+                    ::bevy_spicy_ldtk::private::validate_position(entity, grid_size)?;
+                    // LDTK 0.9 does not export `__worldX`/`__worldY`, so combine with the layer instead
+                    let world_position_px = context.layer_origin_px + position_px;
+                    let layer_order = context.layer_order;
+                    let tile = ::bevy_spicy_ldtk::private::entity_tile(entity);
                     let fields = <#custom_ident as ::bevy_spicy_ldtk::DeserializeLdtkFields>::deserialize_ldtk(&entity.field_instances)?;
 
                     Ok(#ident {
-                        dimensions_px, position_cell, position_px, pivot, fields
+                        dimensions_px, position_cell, position_px, pivot, grid_size, world_position_px, layer_order, tile, fields
                     })
                 }
             }
+
+            impl ::std::convert::TryFrom<&::bevy_spicy_ldtk::private::ldtk2::EntityInstance> for #ident {
+                type Error = ::bevy_spicy_ldtk::error::LdtkError;
+
+                /// Parses the entity with a default `EntityContext`
+                fn try_from(entity: &::bevy_spicy_ldtk::private::ldtk2::EntityInstance) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
+                    Self::from_instance(entity, &::bevy_spicy_ldtk::EntityContext::default())
+                }
+            }
         }
     });
 
-    let entity_identifiers = ldtk_entities.iter().map(|def| &def.identifier);
+    let ref entity_identifiers = ldtk_entities
+        .iter()
+        .map(|def| &def.identifier)
+        .collect::<Vec<_>>();
     let (ref entity_group_names, ref entity_group_types): (Vec<Ident>, Vec<Ident>) = ldtk_entities
         .iter()
         .map(|def| {
@@ -311,14 +853,113 @@ fn define_entities(ldtk_entities: &[EntityDefinition]) -> TokenStream {
         }
 
 
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ProjectEntityKind {
+            #(#entity_group_types),*
+        }
+
+        impl ProjectEntityKind {
+            /// Returns the identifier of this entity as written in LDTK
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    #(ProjectEntityKind::#entity_group_types => #entity_identifiers),*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for ProjectEntityKind {
+            type Err = ::bevy_spicy_ldtk::error::LdtkError;
+
+            fn from_str(identifier: &str) -> Result<Self, Self::Err> {
+                match identifier {
+                    #(#entity_identifiers => Ok(ProjectEntityKind::#entity_group_types),)*
+                    unknown => Err(::bevy_spicy_ldtk::error::LdtkError::UnknownEntityType(unknown.to_string())),
+                }
+            }
+        }
+
+        impl ProjectEntities {
+            /// Groups all entities by the cell they are placed in
+            ///
+            /// The grid is built anew on every call, keep it around to look up many cells.
+            pub fn entity_grid(&self) -> ::bevy_spicy_ldtk::EntityGrid {
+                let grid = ::std::iter::empty::<(::bevy::math::IVec2, ::bevy_spicy_ldtk::EntityIndex)>();
+
+                #(
+                    let grid = grid.chain(self.#entity_group_names.iter().enumerate().map(|(index, entity)| {
+                        (entity.position_cell, ::bevy_spicy_ldtk::EntityIndex {
+                            identifier: #entity_identifiers,
+                            index,
+                        })
+                    }));
+                )*
+
+                grid.collect()
+            }
+        }
+
+        /// Queries over the entities of all levels
+        pub trait ProjectEntityQueries {
+            #(
+                fn #entity_group_names(&self) -> Box<dyn Iterator<Item = (&::bevy_spicy_ldtk::Level<LevelFields, ProjectEntities, Layers>, &#entity_group_types)> + '_>;
+            )*
+
+            /// The number of entities of the given kind across all levels and layers
+            fn entity_count_of(&self, kind: ProjectEntityKind) -> usize;
+        }
+
+        impl ProjectEntityQueries for LdtkWorld {
+            #(
+                fn #entity_group_names(&self) -> Box<dyn Iterator<Item = (&::bevy_spicy_ldtk::Level<LevelFields, ProjectEntities, Layers>, &#entity_group_types)> + '_> {
+                    Box::new(self.levels.iter().flat_map(|level| {
+                        level.layers.entities().flat_map(move |entities| {
+                            entities.#entity_group_names.iter().map(move |entity| (level, entity))
+                        })
+                    }))
+                }
+            )*
+
+            fn entity_count_of(&self, kind: ProjectEntityKind) -> usize {
+                self.entity_count_of_identifier(kind.as_str())
+            }
+        }
+
+        /// Access to the project resource from within systems
+        #[derive(::bevy::ecs::system::SystemParam)]
+        pub struct ProjectParam<'a> {
+            pub project: ::bevy::ecs::system::Res<'a, LdtkWorld>,
+            current_level: Option<::bevy::ecs::system::Res<'a, ::bevy_spicy_ldtk::CurrentLevel>>,
+        }
+
+        impl<'a> ProjectParam<'a> {
+            pub fn level_by_name(&self, identifier: &str) -> Option<&::bevy_spicy_ldtk::Level<LevelFields, ProjectEntities, Layers>> {
+                self.project.level_by_identifier(identifier)
+            }
+
+            /// Returns the level named by the `CurrentLevel` resource, if there is one
+            pub fn current_level(&self) -> Option<&::bevy_spicy_ldtk::Level<LevelFields, ProjectEntities, Layers>> {
+                let current_level = self.current_level.as_ref()?;
+                self.project.level_by_identifier(&current_level.0)
+            }
+
+            #(
+                pub fn #entity_group_names(&self) -> Box<dyn Iterator<Item = (&::bevy_spicy_ldtk::Level<LevelFields, ProjectEntities, Layers>, &#entity_group_types)> + '_> {
+                    ProjectEntityQueries::#entity_group_names(&*self.project)
+                }
+            )*
+        }
+
         impl ::bevy_spicy_ldtk::DeserializeLdtkEntities for ProjectEntities {
-            fn deserialize_ldtk(instances: &[::bevy_spicy_ldtk::private::ldtk2::EntityInstance], parent_size_grid: ::bevy::math::IVec2,  parent_size_px: ::bevy::math::IVec2) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
+            // `context` goes unused when every entity is skipped
+            #[allow(unused_variables)]
+            fn deserialize_ldtk(instances: &[::bevy_spicy_ldtk::private::ldtk2::EntityInstance], context: &::bevy_spicy_ldtk::EntityContext) -> ::bevy_spicy_ldtk::error::LdtkResult<Self> {
 
                 #(let mut #entity_group_names = vec![];)*
 
                 for entity in instances {
                     match entity.identifier.as_str() {
-                        #(#entity_identifiers => #entity_group_names .push(<#entity_group_types>::load(&entity, parent_size_grid, parent_size_px)?),)*
+                        #(#entity_identifiers => #entity_group_names .push(<#entity_group_types>::load(&entity, context)?),)*
+                        #skipped_entities
                         unknown => return Err(::bevy_spicy_ldtk::error::LdtkError::UnknownEntityType(unknown.to_string())),
                     }
                 }
@@ -329,12 +970,171 @@ fn define_entities(ldtk_entities: &[EntityDefinition]) -> TokenStream {
                     }
                 )
             }
+
+            fn counts(&self) -> Vec<(&'static str, usize)> {
+                vec![#((#entity_identifiers, self.#entity_group_names.len())),*]
+            }
+
+            fn bounds(&self) -> Vec<::bevy_spicy_ldtk::EntityBounds> {
+                #[allow(unused_mut)]
+                let mut bounds = vec![];
+
+                #(
+                    bounds.extend(self.#entity_group_names.iter().map(|entity| ::bevy_spicy_ldtk::EntityBounds {
+                        identifier: #entity_identifiers,
+                        position_px: entity.position_px,
+                        dimensions_px: entity.dimensions_px,
+                        pivot: entity.pivot,
+                    }));
+                )*
+
+                bounds
+            }
         }
 
         #(#entities)*
     }
 }
 
+fn define_entity_meta(def: &EntityDefinition) -> TokenStream {
+    let identifier = &def.identifier;
+    let (r, g, b) = match parse_hex_color(&def.color) {
+        Some(color) => color,
+        None => {
+            emit_call_site_error!(
+                "entity '{}' has an invalid color '{}'",
+                def.identifier,
+                def.color
+            );
+            (0, 0, 0)
+        }
+    };
+    let width = def.width as i32;
+    let height = def.height as i32;
+    let pivot_x = def.pivot_x as f32;
+    let pivot_y = 1.0 - def.pivot_y as f32;
+    let tags = &def.tags;
+
+    quote! {
+        ::bevy_spicy_ldtk::EntityMeta {
+            identifier: #identifier,
+            color: ::bevy::render::color::Color::rgb_u8(#r, #g, #b),
+            dimensions_px: ::bevy::math::IVec2::new(#width, #height),
+            pivot: ::bevy::math::Vec2::new(#pivot_x, #pivot_y),
+            tags: &[#(#tags),*],
+        }
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+
+    Some((r, g, b))
+}
+
+/// Returns the default value of a field as JSON, in the same shape as a field instance value
+///
+/// LDTK stores defaults as `{"id": "V_Int", "params": [5]}`, with colors being stored as integers.
+fn field_default(field: &FieldDefinition) -> Option<String> {
+    let default = field.default_override.as_ref()?;
+    let value = default.get("params")?.get(0)?;
+
+    let value = match value.as_i64() {
+        Some(color) if field.field_definition_type == "Color" => {
+            serde_json::Value::String(format!("#{:06X}", color))
+        }
+        _ => value.clone(),
+    };
+
+    Some(value.to_string())
+}
+
+/// Colors are stored as hex strings by LDTK and need to be converted before deserializing
+fn field_parser(field: &FieldDefinition) -> TokenStream {
+    match field.field_definition_type.as_str() {
+        "Color" | "Array<Color>" => quote! { ::bevy_spicy_ldtk::private::parse_color_field },
+        "FilePath" | "Array<FilePath>" => quote! {
+            (|value: &::bevy_spicy_ldtk::private::serde_json::Value| {
+                ::bevy_spicy_ldtk::private::parse_file_path_field(value, PROJECT_DIR)
+            })
+        },
+        _ => quote! { ::bevy_spicy_ldtk::private::parse_field },
+    }
+}
+
+/// The value of an unset nullable field, an empty array for arrays with nullable elements
+fn null_value(field: &FieldDefinition) -> Option<TokenStream> {
+    if !field.can_be_null {
+        None
+    } else if field.field_definition_type.starts_with("Array<") {
+        Some(quote! { Vec::new() })
+    } else {
+        Some(quote! { None })
+    }
+}
+
+/// Checks numeric fields against their bounds, only enforced with the `validate_fields` feature
+fn field_validator(field: &FieldDefinition) -> TokenStream {
+    let is_numeric = matches!(
+        field.field_definition_type.as_str(),
+        "Int" | "Float" | "Array<Int>" | "Array<Float>"
+    );
+
+    if !is_numeric || (field.min.is_none() && field.max.is_none()) {
+        return quote! {};
+    }
+
+    let identifier = &field.identifier;
+    let min = match field.min {
+        Some(min) => quote! { Some(#min) },
+        None => quote! { None },
+    };
+    let max = match field.max {
+        Some(max) => quote! { Some(#max) },
+        None => quote! { None },
+    };
+
+    quote! {
+        ::bevy_spicy_ldtk::private::validate_range(#identifier, &value, #min, #max)?;
+    }
+}
+
+fn define_field_meta(field: &FieldDefinition) -> TokenStream {
+    let identifier = &field.identifier;
+    let kind = &field.field_definition_type;
+    let can_be_null = field.can_be_null;
+    let default = match field.default_override.as_ref().map(serde_json::to_string) {
+        Some(Ok(default)) => quote! { Some(#default) },
+        _ => quote! { None },
+    };
+    let min = match field.min {
+        Some(min) => quote! { Some(#min) },
+        None => quote! { None },
+    };
+    let max = match field.max {
+        Some(max) => quote! { Some(#max) },
+        None => quote! { None },
+    };
+
+    quote! {
+        ::bevy_spicy_ldtk::FieldMeta {
+            identifier: #identifier,
+            kind: #kind,
+            can_be_null: #can_be_null,
+            default: #default,
+            min: #min,
+            max: #max,
+        }
+    }
+}
+
 fn define_fields(field_defs: &[FieldDefinition]) -> Vec<(Ident, TokenStream)> {
     field_defs
         .iter()
@@ -353,11 +1153,18 @@ fn define_fields(field_defs: &[FieldDefinition]) -> Vec<(Ident, TokenStream)> {
             let kind = match field_kind {
                 "Int" => quote! {i64},
                 "Float" => quote! {f64},
-                "String" => quote! {String},
-                "FilePath" => quote! {PathBuf},
+                "String" | "Multilines" => quote! {String},
+                "FilePath" => quote! {::std::path::PathBuf},
                 "Bool" => quote! {bool},
-                "Color" => quote! {::bevy::render::color::Color},
+                "Color" => quote! {FieldColor},
                 "Point" => quote! {::bevy::math::Vec2},
+                "EntityRef" => {
+                    emit_call_site_error!(
+                        "field '{}' is an EntityRef, which needs the entity iids of LDTK 1.0",
+                        field.identifier
+                    );
+                    quote! {}
+                }
                 name if name.starts_with("LocalEnum.") => {
                     let local_enum =
                         format_ident!("{}", name["LocalEnum.".len()..].to_camel_case());
@@ -365,22 +1172,24 @@ fn define_fields(field_defs: &[FieldDefinition]) -> Vec<(Ident, TokenStream)> {
                     quote! {enums::#local_enum}
                 }
                 kind => {
-                    emit_call_site_error!(format!(
-                        "Could not parse kind: \"{}\". Is this library outdated?",
+                    emit_call_site_error!(
+                        "field '{}' has unsupported type '{}'. Is this library outdated?",
+                        field.identifier,
                         kind
-                    ));
+                    );
                     quote! {}
                 }
             };
 
-            let kind = if is_array {
-                quote! {Vec< #kind >}
+            // For arrays LDTK allows null elements, the array itself is always present
+            let kind = if can_be_null {
+                quote! {Option< #kind >}
             } else {
                 kind
             };
 
-            let kind = if can_be_null {
-                quote! {Option< #kind >}
+            let kind = if is_array {
+                quote! {Vec< #kind >}
             } else {
                 kind
             };
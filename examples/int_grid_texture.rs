@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
     render::texture::{Extent3d, FilterMode, TextureDimension, TextureFormat},
 };
-use bevy_spicy_ldtk::{ldtk, DeserializeLdtk, SpecialLayerDefinitions, SpecialValues};
+use bevy_spicy_ldtk::{ldtk, DeserializeLdtk};
 
 ldtk! {pub levels, "assets/int_grid.ldtk"}
 
@@ -12,7 +12,7 @@ fn main() {
     let project = levels::Project::deserialize_ldtk(&data).unwrap();
 
     App::new()
-        .insert_resource(ClearColor(project.levels[0].background_color))
+        .insert_resource(ClearColor(project.levels[0].clear_color()))
         .insert_resource(project)
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup_camera)
@@ -32,34 +32,25 @@ fn generate_texture(
 ) {
     let level = &project.levels[0];
     let layer = &level.layers.int_grid_example_layer;
-    let values = match &layer.special {
-        SpecialValues::IntGrid {
-            values,
-            auto_layer: _,
-        } => values,
-        _ => panic!("Unexpected layer type"),
-    };
+    let layer_definition = &project.layer_definitions[&layer.layer_definition];
 
-    let value_definitions = match &project.layer_definitions[&layer.layer_definition].special {
-        SpecialLayerDefinitions::IntGrid { value_definitions } => value_definitions,
-        _ => panic!("Unexpected layer definition type"),
-    };
+    let dimension = layer.dimensions_cell;
+    let buffer = (0..dimension.y)
+        .flat_map(|y| (0..dimension.x).map(move |x| IVec2::new(x, y)))
+        .flat_map(|cell| {
+            let c = layer
+                .int_grid_color_at(cell, layer_definition)
+                .unwrap_or(Color::NONE);
+            [
+                (c.r() * 255.) as u8,
+                (c.g() * 255.) as u8,
+                (c.b() * 255.) as u8,
+                (c.a() * 255.) as u8,
+            ]
+        })
+        .collect();
 
-    // 0 means "empty" tile
-    let mut colors = vec![[0, 0, 0, 0]];
-    colors.extend(value_definitions.iter().map(|def| {
-        let c = def.color;
-        [
-            (c.r() * 255.) as u8,
-            (c.g() * 255.) as u8,
-            (c.b() * 255.) as u8,
-            (c.a() * 255.) as u8,
-        ]
-    }));
-
-    let buffer = values.iter().flat_map(|i| colors[*i as usize]).collect();
-
-    let dimension = layer.dimensions_cell.as_uvec2();
+    let dimension = dimension.as_uvec2();
     let mut texture = Texture::new(
         Extent3d::new(dimension.x, dimension.y, 1),
         TextureDimension::D2,
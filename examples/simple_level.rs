@@ -1,12 +1,11 @@
-use bevy_spicy_ldtk::{ldtk, DeserializeLdtk};
+use bevy_spicy_ldtk::prelude::*;
 
 ldtk! {pub levels, "assets/levels.ldtk"}
 
 fn main() {
     let data = ldtk2::Ldtk::from_path(levels::FILEPATH).unwrap();
 
-    let project: bevy_spicy_ldtk::World<_, _, _, _> =
-        levels::Project::deserialize_ldtk(&data).unwrap();
+    let project: World<_, _, _, _> = levels::Project::deserialize_ldtk(&data).unwrap();
 
     println!("ldtk file: {:?}", project);
 }
@@ -1,15 +1,61 @@
+/// Errors that can occur while loading a LDTK project
+///
+/// New checks on the project come with new variants, so handle unknown ones with a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum LdtkError {
     #[error("An error occured while deserializing")]
     Json(#[from] serde_json::Error),
+    #[error("An error occured while reading a file")]
+    Io(#[from] std::io::Error),
     #[error("An error occured while parsing a color")]
     HexColor(#[from] bevy::render::color::HexColorError),
+    #[error("The field value {value} could not be parsed")]
+    InvalidFieldValue {
+        value: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("The entity {identifier:?} could not be parsed")]
+    InvalidEntity {
+        identifier: String,
+        #[source]
+        source: Box<LdtkError>,
+    },
     #[error("One or more fields are missing in the LDTK file")]
     MissingFieldsForEntities,
     #[error("One or more fields are missing in the LDTK file")]
-    MissingFieldsForLayers,
-    #[error("One or more fields are missing in the LDTK file")]
     MissingFieldsForLevels,
+    #[error("The layer {0:?} is missing in the LDTK file")]
+    MissingLayer(String),
+    #[error("The level {0:?} has no layer instances, but the project defines layers")]
+    LevelWithoutLayers(String),
+    #[error("The layers of level {0:?} are saved in a separate file which was not loaded")]
+    MissingExternalLevel(String),
+    #[error("The value {value} of field {field:?} is outside of its range")]
+    FieldOutOfRange {
+        field: String,
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    #[error("The IntGrid layer {layer:?} should have {expected} cells but has {found}")]
+    GridSizeMismatch {
+        layer: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("The grid and pixel positions of the entity {entity:?} disagree")]
+    EntityPositionMismatch { entity: String },
+    #[error("The {kind} with uid {uid} exists in both merged worlds")]
+    UidCollision { kind: &'static str, uid: i64 },
+    #[error("The {kind} {identifier:?} exists in both merged worlds")]
+    IdentifierCollision {
+        kind: &'static str,
+        identifier: String,
+    },
+    #[error("The tile layer {layer:?} has tiles but no tileset")]
+    TilesWithoutTileset { layer: String },
     #[error("An unknown layer type was encountered")]
     UnknownLayerType(String),
     #[error("An unknown entity type was encountered")]
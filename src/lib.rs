@@ -9,9 +9,19 @@ use bevy::{
 };
 pub use bevy_spicy_ldtk_derive::ldtk;
 use error::{LdtkError, LdtkResult};
+pub use ldtk2;
+use serde::Deserialize;
 
 pub mod error;
 
+pub mod prelude {
+    pub use crate::{
+        ldtk, DeserializeLDtkLayers, DeserializeLdtk, DeserializeLdtkEntities,
+        DeserializeLdtkFields, FieldAccess, IntGrid, Layer, LayerDefinition, LdtkColor, LdtkPlugin,
+        Level, SpecialLayerDefinitions, SpecialValues, Tile, Tileset, World,
+    };
+}
+
 #[derive(Debug)]
 pub struct LdtkPlugin<T: DeserializeLdtk + bevy::asset::Asset>(PhantomData<T>);
 
@@ -46,9 +56,21 @@ impl<T: DeserializeLdtk + bevy::asset::Asset + Send + Sync + 'static> AssetLoade
         load_context: &'a mut bevy::asset::LoadContext,
     ) -> bevy::asset::BoxedFuture<'a, Result<(), anyhow::Error>> {
         Box::pin(async move {
-            let ldtk_text = String::from_utf8(bytes.to_vec())?;
-            let ldtk = ldtk2::Ldtk::from_str(&ldtk_text)?;
-            let ldtk = T::deserialize_ldtk(&ldtk)?;
+            let raw: serde_json::Value = serde_json::from_slice(bytes)?;
+            let mut ldtk = ldtk2::Ldtk::deserialize(&raw)?;
+
+            let base_dir = load_context
+                .path()
+                .parent()
+                .unwrap_or(std::path::Path::new(""));
+            for (index, rel_path) in external_levels(&ldtk) {
+                let level_bytes = load_context
+                    .read_asset_bytes(base_dir.join(rel_path))
+                    .await?;
+                ldtk.levels[index] = serde_json::from_slice(&level_bytes)?;
+            }
+
+            let ldtk = T::deserialize_ldtk_with_raw(&ldtk, raw)?;
 
             load_context.set_default_asset(LoadedAsset::new(ldtk));
             Ok(())
@@ -63,23 +85,478 @@ impl<T: DeserializeLdtk + bevy::asset::Asset + Send + Sync + 'static> AssetLoade
 pub trait DeserializeLDtkLayers: Sized {
     type Entities: DeserializeLdtkEntities;
 
-    fn deserialize_ldtk(instances: &[ldtk2::LayerInstance]) -> LdtkResult<Self>;
+    /// Parses the layers of a level, `level_top_left_px` is the top left corner of the level in
+    /// world pixels, which the layer offsets are relative to
+    fn deserialize_ldtk(
+        instances: &[ldtk2::LayerInstance],
+        level_top_left_px: ::bevy::math::IVec2,
+    ) -> LdtkResult<Self>;
+
+    /// Returns all layers in the order they are defined in
+    fn layers(&self) -> Vec<&Layer<Self::Entities>>;
+
+    /// Returns the layer with the given identifier, or `None` if there is no such layer
+    fn layer(&self, identifier: &str) -> Option<&Layer<Self::Entities>>;
 }
 
+/// Implemented by the generated `ProjectEntities`
 pub trait DeserializeLdtkEntities: Sized {
+    /// Parses the entities of a single layer, `context` describes the layer they are placed in
     fn deserialize_ldtk(
         instances: &[ldtk2::EntityInstance],
-        parent_size_grid: ::bevy::math::IVec2,
-        parent_size_px: ::bevy::math::IVec2,
+        context: &EntityContext,
     ) -> LdtkResult<Self>;
+
+    /// Returns how many entities of each identifier were loaded
+    fn counts(&self) -> Vec<(&'static str, usize)>;
+
+    /// Returns the placement of every loaded entity
+    fn bounds(&self) -> Vec<EntityBounds>;
+}
+
+/// Where a single entity is placed, used to flip its position to y up
+///
+/// With the default context positions are flipped around `y = 0`, so they end up below the origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntityContext {
+    pub parent_size_grid: IVec2,
+    pub parent_size_px: IVec2,
+    pub grid_size: i64,
+    /// The bottom left corner of the layer in world pixels, see [`Level::layer_origin_px`]
+    pub layer_origin_px: IVec2,
+    /// The draw order of the layer, see [`Layer::order`]
+    pub layer_order: i32,
+}
+
+/// Where an entity is placed within its layer, y up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityBounds {
+    pub identifier: &'static str,
+    /// The position of the pivot
+    pub position_px: IVec2,
+    pub dimensions_px: IVec2,
+    pub pivot: ::bevy::math::Vec2,
 }
 
+/// Implemented by the generated `LevelFields` and entity field structs
 pub trait DeserializeLdtkFields: Sized {
+    /// Parses the fields from their instances, which can also be built by hand for tests
     fn deserialize_ldtk(instances: &[ldtk2::FieldInstance]) -> LdtkResult<Self>;
 }
 
+/// Parses field instances into a generated field struct, such as `LevelFields`
+pub fn parse_level_fields<T: DeserializeLdtkFields>(
+    instances: &[ldtk2::FieldInstance],
+) -> LdtkResult<T> {
+    T::deserialize_ldtk(instances)
+}
+
+/// Refers to an entity by its identifier and its position in the matching `all_*` list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntityIndex {
+    pub identifier: &'static str,
+    pub index: usize,
+}
+
+/// The entities of a layer grouped by the cell they are placed in, see `entity_grid`
+///
+/// Building it walks every entity once, so keep it around instead of rebuilding it per lookup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntityGrid {
+    cells: HashMap<IVec2, Vec<EntityIndex>>,
+}
+
+impl EntityGrid {
+    /// Returns all entities placed in `cell`
+    pub fn at(&self, cell: IVec2) -> &[EntityIndex] {
+        self.cells.get(&cell).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Iterates over every occupied cell together with its entities
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &[EntityIndex])> {
+        self.cells
+            .iter()
+            .map(|(cell, entities)| (*cell, entities.as_slice()))
+    }
+}
+
+impl std::iter::FromIterator<(IVec2, EntityIndex)> for EntityGrid {
+    fn from_iter<I: IntoIterator<Item = (IVec2, EntityIndex)>>(iter: I) -> Self {
+        let mut cells: HashMap<IVec2, Vec<EntityIndex>> = HashMap::default();
+        for (cell, entity) in iter {
+            cells.entry(cell).or_default().push(entity);
+        }
+
+        EntityGrid { cells }
+    }
+}
+
+/// Describes a field as it is defined in the LDTK project
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldMeta {
+    pub identifier: &'static str,
+    /// The type as written by LDTK, e.g. `Int` or `Array<LocalEnum.Item>`
+    pub kind: &'static str,
+    pub can_be_null: bool,
+    /// The default override as raw JSON, if one is set
+    pub default: Option<&'static str>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Describes an entity as it is defined in the LDTK project
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityMeta {
+    pub identifier: &'static str,
+    pub color: bevy::render::color::Color,
+    /// The default size of new instances
+    pub dimensions_px: bevy::math::IVec2,
+    /// The pivot, y up
+    pub pivot: bevy::math::Vec2,
+    pub tags: &'static [&'static str],
+}
+
+/// Uniform access to the fields of a generated field struct
+pub trait FieldAccess {
+    /// Returns every field keyed by its identifier as written in LDTK
+    fn fields(&self) -> HashMap<String, FieldValue>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Int(i64),
+    Float(f64),
+    String(String),
+    FilePath(std::path::PathBuf),
+    Bool(bool),
+    Color(bevy::render::color::Color),
+    Point(bevy::math::Vec2),
+    /// The name of the enum variant
+    Enum(String),
+    Array(Vec<FieldValue>),
+    Null,
+}
+
+pub trait ToFieldValue {
+    fn to_field_value(&self) -> FieldValue;
+}
+
+impl ToFieldValue for i64 {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Int(*self)
+    }
+}
+
+impl ToFieldValue for f64 {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Float(*self)
+    }
+}
+
+impl ToFieldValue for String {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::String(self.clone())
+    }
+}
+
+impl ToFieldValue for std::path::PathBuf {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::FilePath(self.clone())
+    }
+}
+
+impl ToFieldValue for bool {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Bool(*self)
+    }
+}
+
+impl ToFieldValue for bevy::render::color::Color {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Color(*self)
+    }
+}
+
+/// A color type that `Color` fields can be parsed into, see the `color_type` option of `ldtk!`
+///
+/// Implementations also need [`ToFieldValue`], which usually converts into [`FieldValue::Color`].
+pub trait LdtkColor: ToFieldValue + Sized {
+    /// Creates the color from its red, green, blue and alpha channels
+    fn from_rgba8(rgba: [u8; 4]) -> Self;
+}
+
+impl LdtkColor for bevy::render::color::Color {
+    fn from_rgba8([r, g, b, a]: [u8; 4]) -> Self {
+        bevy::render::color::Color::rgba_u8(r, g, b, a)
+    }
+}
+
+impl ToFieldValue for bevy::math::Vec2 {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Point(*self)
+    }
+}
+
+impl<T: ToFieldValue> ToFieldValue for Vec<T> {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Array(self.iter().map(ToFieldValue::to_field_value).collect())
+    }
+}
+
+impl<T: ToFieldValue> ToFieldValue for Option<T> {
+    fn to_field_value(&self) -> FieldValue {
+        self.as_ref()
+            .map(ToFieldValue::to_field_value)
+            .unwrap_or(FieldValue::Null)
+    }
+}
+
+/// A rectangle of a tileset, as used to draw entities in the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileRect {
+    pub tileset_uid: i64,
+    /// The top left corner in pixels, y down as in the tileset image
+    pub x: i64,
+    pub y: i64,
+    pub w: i64,
+    pub h: i64,
+}
+
+/// The raw `realEditorValues` of every field, keyed by the field identifier as written in LDTK
+pub type RawEditorValues = HashMap<String, Vec<Option<serde_json::Value>>>;
+
 pub trait DeserializeLdtk: Sized {
     fn deserialize_ldtk(ldtk: &ldtk2::Coordinate) -> LdtkResult<Self>;
+
+    /// Like `deserialize_ldtk`, with `raw` being the JSON of the project file `ldtk` was parsed from
+    ///
+    /// The other constructors go through this, so that with the `keep_raw` feature the JSON is
+    /// kept as it was read, including keys `ldtk2` does not know about.
+    fn deserialize_ldtk_with_raw(
+        ldtk: &ldtk2::Coordinate,
+        _raw: serde_json::Value,
+    ) -> LdtkResult<Self> {
+        Self::deserialize_ldtk(ldtk)
+    }
+
+    /// Parses the LDTK project from the JSON in `bytes`
+    ///
+    /// Levels saved in separate files are not read, so such projects fail with
+    /// `MissingExternalLevel`. Use `deserialize_ldtk_from_slice_with` to provide their bytes.
+    fn deserialize_ldtk_from_slice(bytes: &[u8]) -> LdtkResult<Self> {
+        let raw: serde_json::Value = serde_json::from_slice(bytes)?;
+        let ldtk = ldtk2::Ldtk::deserialize(&raw)?;
+        Self::deserialize_ldtk_with_raw(&ldtk, raw)
+    }
+
+    /// Parses the LDTK project from the JSON in `bytes`, reading levels saved in separate files
+    /// through `read_level`
+    ///
+    /// `read_level` is given the path of a level file as written in the project, relative to it.
+    fn deserialize_ldtk_from_slice_with(
+        bytes: &[u8],
+        mut read_level: impl FnMut(&std::path::Path) -> LdtkResult<Vec<u8>>,
+    ) -> LdtkResult<Self> {
+        let raw: serde_json::Value = serde_json::from_slice(bytes)?;
+        let mut ldtk = ldtk2::Ldtk::deserialize(&raw)?;
+
+        for (index, rel_path) in external_levels(&ldtk) {
+            let bytes = read_level(std::path::Path::new(&rel_path))?;
+            ldtk.levels[index] = serde_json::from_slice(&bytes)?;
+        }
+
+        Self::deserialize_ldtk_with_raw(&ldtk, raw)
+    }
+
+    /// Parses the LDTK project from the JSON read from `reader`
+    ///
+    /// Levels saved in separate files are not read, so such projects fail with
+    /// `MissingExternalLevel`. Use `deserialize_ldtk_from_slice_with` to provide their bytes.
+    fn deserialize_ldtk_from_reader<R: std::io::Read>(reader: R) -> LdtkResult<Self> {
+        let raw: serde_json::Value = serde_json::from_reader(reader)?;
+        let ldtk = ldtk2::Ldtk::deserialize(&raw)?;
+        Self::deserialize_ldtk_with_raw(&ldtk, raw)
+    }
+
+    /// Parses the LDTK project at `path`, loading levels saved in separate files next to it
+    fn deserialize_ldtk_from_path(path: impl AsRef<std::path::Path>) -> LdtkResult<Self> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or(std::path::Path::new(""));
+
+        Self::deserialize_ldtk_from_slice_with(&std::fs::read(path)?, |rel_path| {
+            Ok(std::fs::read(base_dir.join(rel_path))?)
+        })
+    }
+}
+
+/// Sums up the entities of each identifier across all levels and layers
+fn count_entities<
+    LevelFields: DeserializeLdtkFields,
+    Entities: DeserializeLdtkEntities,
+    Layers: DeserializeLDtkLayers<Entities = Entities>,
+>(
+    levels: &[Level<LevelFields, Entities, Layers>],
+) -> HashMap<&'static str, usize> {
+    let mut entity_counts = HashMap::default();
+    for level in levels {
+        for layer in level.layers.layers() {
+            if let SpecialValues::Entities(entities) = &layer.special {
+                for (identifier, count) in entities.counts() {
+                    *entity_counts.entry(identifier).or_insert(0) += count;
+                }
+            }
+        }
+    }
+    entity_counts
+}
+
+/// Returns the index and relative path of every level whose layers are saved in a separate file
+///
+/// Levels which still have their layers inline are left out, even if they name a file.
+fn external_levels(ldtk: &ldtk2::Ldtk) -> Vec<(usize, String)> {
+    ldtk.levels
+        .iter()
+        .enumerate()
+        .filter(|(_, level)| level.layer_instances.is_none())
+        .filter_map(|(index, level)| Some((index, level.external_rel_path.clone()?)))
+        .collect()
+}
+
+/// The size of a world grid cell, if the project uses the GridVania layout
+fn world_grid_size(ldtk: &ldtk2::Ldtk) -> Option<IVec2> {
+    match ldtk.world_layout {
+        ldtk2::WorldLayout::GridVania => Some(IVec2::new(
+            ldtk.world_grid_width as i32,
+            ldtk.world_grid_height as i32,
+        )),
+        _ => None,
+    }
+}
+
+/// The bottom left corner of a level in world pixels, y up
+fn level_world_position_px(ldtk_level: &ldtk2::Level, dimensions_px: IVec2) -> IVec2 {
+    // Outside of the Free and GridVania layouts LDTK leaves the position unset as `-1`
+    let (world_x, world_y) = if ldtk_level.world_x == -1 && ldtk_level.world_y == -1 {
+        bevy::log::warn!(
+            "Level {:?} has no world position, placing it at the origin",
+            ldtk_level.identifier
+        );
+        (0, 0)
+    } else {
+        (ldtk_level.world_x as i32, ldtk_level.world_y as i32)
+    };
+
+    IVec2::new(world_x, -world_y - dimensions_px.y)
+}
+
+/// A view of a project that borrows its strings from the parsed file instead of cloning them
+///
+/// Only the level and tileset metadata is borrowed. Fields, layers and entities are parsed into
+/// the owned generated types when a level is loaded with [`LevelRef::load`].
+#[derive(Debug, Clone)]
+pub struct WorldRef<'a> {
+    pub levels: Vec<LevelRef<'a>>,
+    pub tilesets: HashMap<i64, TilesetRef<'a>>,
+    world_grid_size: Option<IVec2>,
+}
+
+impl<'a> WorldRef<'a> {
+    pub fn new(ldtk: &'a ldtk2::Ldtk) -> Self {
+        let world_grid_size = world_grid_size(ldtk);
+
+        let levels = ldtk.levels.iter().map(LevelRef::new).collect();
+
+        let tilesets = ldtk
+            .defs
+            .tilesets
+            .iter()
+            .map(|tileset| (tileset.uid, TilesetRef::new(tileset)))
+            .collect();
+
+        WorldRef {
+            levels,
+            tilesets,
+            world_grid_size,
+        }
+    }
+
+    pub fn level_by_identifier(&self, identifier: &str) -> Option<&LevelRef<'a>> {
+        self.levels
+            .iter()
+            .find(|level| level.identifier == identifier)
+    }
+
+    pub fn tileset_by_identifier(&self, identifier: &str) -> Option<&TilesetRef<'a>> {
+        self.tilesets
+            .values()
+            .find(|tileset| tileset.ident == identifier)
+    }
+
+    /// Parses a level into the owned generated types
+    pub fn load_level<
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    >(
+        &self,
+        level: &LevelRef<'a>,
+    ) -> LdtkResult<Level<LevelFields, Entities, Layers>> {
+        Level::load(level.source, self.world_grid_size)
+    }
+}
+
+/// The metadata of a level, borrowed from the parsed file
+#[derive(Debug, Clone, Copy)]
+pub struct LevelRef<'a> {
+    pub identifier: &'a str,
+    pub id: i64,
+    pub dimensions_px: IVec2,
+    pub world_position_px: IVec2,
+    pub background_image_path: Option<&'a str>,
+    source: &'a ldtk2::Level,
+}
+
+impl<'a> LevelRef<'a> {
+    fn new(ldtk_level: &'a ldtk2::Level) -> Self {
+        let dimensions_px = IVec2::new(ldtk_level.px_wid as i32, ldtk_level.px_hei as i32);
+
+        LevelRef {
+            identifier: &ldtk_level.identifier,
+            id: ldtk_level.uid,
+            dimensions_px,
+            world_position_px: level_world_position_px(ldtk_level, dimensions_px),
+            background_image_path: ldtk_level.bg_rel_path.as_deref(),
+            source: ldtk_level,
+        }
+    }
+}
+
+/// The metadata of a tileset, borrowed from the parsed file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilesetRef<'a> {
+    pub grid_size: i64,
+    pub ident: &'a str,
+    pub padding: i64,
+    pub spacing: i64,
+    pub dimensions_cell: IVec2,
+    pub dimensions_px: IVec2,
+    pub rel_path: &'a str,
+    pub id: i64,
+}
+
+impl<'a> TilesetRef<'a> {
+    fn new(tileset: &'a ldtk2::TilesetDefinition) -> Self {
+        TilesetRef {
+            grid_size: tileset.tile_grid_size,
+            ident: &tileset.identifier,
+            padding: tileset.padding,
+            spacing: tileset.spacing,
+            dimensions_cell: IVec2::new(tileset.c_wid as i32, tileset.c_hei as i32),
+            dimensions_px: IVec2::new(tileset.px_wid as i32, tileset.px_hei as i32),
+            rel_path: &tileset.rel_path,
+            id: tileset.uid,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -92,10 +569,199 @@ pub struct World<
     pub levels: Vec<Level<LevelFields, Entities, Layers>>,
     pub tilesets: HashMap<i64, Tileset>,
     pub layer_definitions: HashMap<i64, LayerDefinition>,
+    tileset_identifiers: HashMap<String, i64>,
+    world_grid_size: Option<IVec2>,
+    entity_counts: HashMap<&'static str, usize>,
+    json_version: String,
+    #[cfg(feature = "keep_raw")]
+    raw: serde_json::Value,
     _entities: PhantomData<Entities>,
     _world_type: PhantomData<WorldType>,
 }
 
+impl<
+        WorldType: TypeUuid,
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > World<WorldType, LevelFields, Entities, Layers>
+{
+    /// Iterates over the levels row by row from the top, and left to right within a row
+    pub fn levels_sorted(&self) -> impl Iterator<Item = &Level<LevelFields, Entities, Layers>> {
+        let mut levels = self.levels.iter().collect::<Vec<_>>();
+        levels.sort_by_key(|level| {
+            let top = level.world_position_px.y + level.dimensions_px.y;
+            (std::cmp::Reverse(top), level.world_position_px.x)
+        });
+        levels.into_iter()
+    }
+
+    /// Iterates over every layer of every level
+    pub fn iter_layers(
+        &self,
+    ) -> impl Iterator<Item = (&Level<LevelFields, Entities, Layers>, &Layer<Entities>)> {
+        self.levels.iter().flat_map(|level| {
+            level
+                .layers
+                .layers()
+                .into_iter()
+                .map(move |layer| (level, layer))
+        })
+    }
+
+    /// Returns the level containing `point`, given in world pixels with y up
+    ///
+    /// LDTK 0.9 has no world depth, so for overlapping levels the one listed last is returned.
+    pub fn level_at(&self, point: IVec2) -> Option<&Level<LevelFields, Entities, Layers>> {
+        self.levels.iter().rev().find(|level| {
+            let min = level.world_position_px;
+            let max = min + level.dimensions_px;
+
+            point.x >= min.x && point.y >= min.y && point.x < max.x && point.y < max.y
+        })
+    }
+
+    /// Loads a single level stored in a separate `.ldtkl` file of this project
+    pub fn load_level(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> LdtkResult<Level<LevelFields, Entities, Layers>> {
+        Level::load_from_path(path, self.world_grid_size)
+    }
+
+    /// All tileset and level background images of this project, sorted and without duplicates
+    ///
+    /// The paths are joined with `project_dir`, usually the generated `PROJECT_DIR`, so that they
+    /// can be given to the asset server for preloading.
+    pub fn referenced_assets(
+        &self,
+        project_dir: impl AsRef<std::path::Path>,
+    ) -> Vec<std::path::PathBuf> {
+        let project_dir = project_dir.as_ref();
+
+        let tileset_paths = self.tilesets.values().map(|tileset| &tileset.rel_path);
+        let background_paths = self
+            .levels
+            .iter()
+            .filter_map(|level| level.background_image_path.as_ref());
+
+        let mut assets = tileset_paths
+            .chain(background_paths)
+            .filter(|path| !path.is_empty())
+            .map(|path| project_dir.join(path))
+            .collect::<Vec<_>>();
+        assets.sort();
+        assets.dedup();
+
+        assets
+    }
+
+    pub fn level_by_identifier(
+        &self,
+        identifier: &str,
+    ) -> Option<&Level<LevelFields, Entities, Layers>> {
+        self.levels
+            .iter()
+            .find(|level| level.identifier == identifier)
+    }
+
+    pub fn tileset_by_identifier(&self, identifier: &str) -> Option<&Tileset> {
+        self.tileset_identifiers
+            .get(identifier)
+            .and_then(|uid| self.tilesets.get(uid))
+    }
+
+    /// Combines the levels, tilesets and layer definitions of two worlds
+    ///
+    /// Both worlds have to be generated by the same macro invocation. Fails if a level, tileset
+    /// or layer definition uid, or a tileset identifier is used in both of them. The GridVania grid
+    /// and, with `keep_raw`, the raw project of `self` are kept.
+    pub fn merge(mut self, other: Self) -> LdtkResult<Self> {
+        if let Some(level) = other
+            .levels
+            .iter()
+            .find(|level| self.levels.iter().any(|own| own.id == level.id))
+        {
+            return Err(LdtkError::UidCollision {
+                kind: "level",
+                uid: level.id,
+            });
+        }
+        if let Some(uid) = other
+            .tilesets
+            .keys()
+            .find(|uid| self.tilesets.contains_key(uid))
+        {
+            return Err(LdtkError::UidCollision {
+                kind: "tileset",
+                uid: *uid,
+            });
+        }
+        if let Some(uid) = other
+            .layer_definitions
+            .keys()
+            .find(|uid| self.layer_definitions.contains_key(uid))
+        {
+            return Err(LdtkError::UidCollision {
+                kind: "layer definition",
+                uid: *uid,
+            });
+        }
+        if let Some(identifier) = other
+            .tileset_identifiers
+            .keys()
+            .find(|identifier| self.tileset_identifiers.contains_key(*identifier))
+        {
+            return Err(LdtkError::IdentifierCollision {
+                kind: "tileset",
+                identifier: identifier.clone(),
+            });
+        }
+
+        self.levels.extend(other.levels);
+        self.tilesets.extend(other.tilesets);
+        self.layer_definitions.extend(other.layer_definitions);
+        self.tileset_identifiers.extend(other.tileset_identifiers);
+        for (identifier, count) in other.entity_counts {
+            *self.entity_counts.entry(identifier).or_insert(0) += count;
+        }
+
+        Ok(self)
+    }
+
+    /// The project as it was read from the LDTK file, for data the typed API does not cover
+    #[cfg(feature = "keep_raw")]
+    pub fn raw(&self) -> &serde_json::Value {
+        &self.raw
+    }
+
+    /// Spawns an entity with a [`LevelComponent`] for every level, in the order of `levels`
+    pub fn spawn_levels(
+        &self,
+        commands: &mut bevy::ecs::system::Commands,
+    ) -> Vec<bevy::ecs::entity::Entity> {
+        self.levels
+            .iter()
+            .map(|level| commands.spawn().insert(level.component()).id())
+            .collect()
+    }
+
+    /// The LDTK version the project was saved with, to compare against `LDTK_JSON_VERSION`
+    pub fn json_version(&self) -> &str {
+        &self.json_version
+    }
+
+    /// The number of entities across all levels and layers
+    pub fn entity_count(&self) -> usize {
+        self.entity_counts.values().sum()
+    }
+
+    /// The number of entities with the given identifier across all levels and layers
+    pub fn entity_count_of_identifier(&self, identifier: &str) -> usize {
+        self.entity_counts.get(identifier).copied().unwrap_or(0)
+    }
+}
+
 impl<
         WorldType: TypeUuid,
         LevelFields: DeserializeLdtkFields,
@@ -103,13 +769,84 @@ impl<
         Layers: DeserializeLDtkLayers<Entities = Entities>,
     > DeserializeLdtk for World<WorldType, LevelFields, Entities, Layers>
 {
+    /// With the `keep_raw` feature the raw JSON is recreated from `ldtk`, which loses keys that
+    /// `ldtk2` does not know about. Prefer the other constructors, which keep the JSON as read.
     fn deserialize_ldtk(ldtk: &ldtk2::Ldtk) -> LdtkResult<Self> {
+        #[cfg(feature = "keep_raw")]
+        let raw = serde_json::to_value(ldtk)?;
+        #[cfg(not(feature = "keep_raw"))]
+        let raw = serde_json::Value::Null;
+
+        Self::deserialize_ldtk_with_raw(ldtk, raw)
+    }
+
+    fn deserialize_ldtk_with_raw(
+        ldtk: &ldtk2::Ldtk,
+        #[cfg(feature = "keep_raw")] raw: serde_json::Value,
+        #[cfg(not(feature = "keep_raw"))] _raw: serde_json::Value,
+    ) -> LdtkResult<Self> {
+        let world_grid_size = world_grid_size(ldtk);
+
         let levels = ldtk
             .levels
             .iter()
-            .map(Level::load)
-            .collect::<LdtkResult<_>>()?;
+            .map(|level| Level::load(level, world_grid_size))
+            .collect::<LdtkResult<Vec<_>>>()?;
+
+        let entity_counts = count_entities(&levels);
+
+        let DefinitionsOnly {
+            tilesets,
+            layer_definitions,
+            ..
+        } = Self::load_definitions_only(ldtk)?;
+
+        let tileset_identifiers = tilesets
+            .values()
+            .map(|tileset| (tileset.ident.clone(), tileset.id))
+            .collect();
+
+        Ok(World {
+            levels,
+            tilesets,
+            layer_definitions,
+            tileset_identifiers,
+            world_grid_size,
+            entity_counts,
+            json_version: ldtk.json_version.clone(),
+            #[cfg(feature = "keep_raw")]
+            raw,
+            _entities: PhantomData,
+            _world_type: PhantomData,
+        })
+    }
+}
+/// The definitions of a project, loaded without any of its levels
+#[derive(Debug)]
+pub struct DefinitionsOnly {
+    pub tilesets: HashMap<i64, Tileset>,
+    pub layer_definitions: HashMap<i64, LayerDefinition>,
+    pub enums: Vec<EnumDefinition>,
+}
+
+/// An enum as it is defined in the LDTK project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumDefinition {
+    pub identifier: String,
+    pub uid: i64,
+    /// The values in the order they are defined in
+    pub values: Vec<String>,
+}
 
+impl<
+        WorldType: TypeUuid,
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > World<WorldType, LevelFields, Entities, Layers>
+{
+    /// Loads only the tilesets, layer definitions and enums, skipping every level
+    pub fn load_definitions_only(ldtk: &ldtk2::Ldtk) -> LdtkResult<DefinitionsOnly> {
         let tilesets = ldtk
             .defs
             .tilesets
@@ -124,15 +861,25 @@ impl<
             .map(|def| Ok((def.uid, LayerDefinition::load(def)?)))
             .collect::<LdtkResult<_>>()?;
 
-        Ok(World {
-            levels,
+        let enums = ldtk
+            .defs
+            .enums
+            .iter()
+            .map(|def| EnumDefinition {
+                identifier: def.identifier.clone(),
+                uid: def.uid,
+                values: def.values.iter().map(|value| value.id.clone()).collect(),
+            })
+            .collect();
+
+        Ok(DefinitionsOnly {
             tilesets,
             layer_definitions,
-            _entities: PhantomData,
-            _world_type: PhantomData,
+            enums,
         })
     }
 }
+
 impl<
         WorldType: TypeUuid,
         LevelFields: DeserializeLdtkFields,
@@ -143,18 +890,75 @@ impl<
     const TYPE_UUID: bevy::reflect::Uuid = WorldType::TYPE_UUID;
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Tile {
     pub flip_x: bool,
     pub flip_y: bool,
     pub position_px: ::bevy::math::IVec2,
     pub src_px: ::bevy::math::IVec2,
     pub id: i64,
+    /// LDTK 0.9 has no per tile alpha, so this is always fully opaque
+    pub alpha: f32,
+}
+
+/// The four orientations a tile can be placed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileFlip {
+    None,
+    X,
+    Y,
+    XY,
 }
 
 impl Tile {
-    fn load(tile: &ldtk2::TileInstance, layer_dimensions_px: IVec2) -> LdtkResult<Self> {
-        let flip_x = tile.f & 0x1 == 0x1;
+    pub fn orientation(&self) -> TileFlip {
+        match (self.flip_x, self.flip_y) {
+            (false, false) => TileFlip::None,
+            (true, false) => TileFlip::X,
+            (false, true) => TileFlip::Y,
+            (true, true) => TileFlip::XY,
+        }
+    }
+
+    /// Combines the alpha of this tile with the opacity of its layer
+    pub fn effective_alpha<EntityFields>(&self, layer: &Layer<EntityFields>) -> f32 {
+        (self.alpha * layer.opacity as f32).clamp(0.0, 1.0)
+    }
+
+    /// A white color with the effective alpha, to be used as a tint
+    pub fn tint<EntityFields>(&self, layer: &Layer<EntityFields>) -> bevy::render::color::Color {
+        bevy::render::color::Color::rgba(1.0, 1.0, 1.0, self.effective_alpha(layer))
+    }
+
+    /// The corners of this tile in `tileset` as UVs between `0.0` and `1.0`, y down like the image
+    ///
+    /// The size comes from the grid of the tileset, which can differ from the grid of the layer
+    /// that places the tile.
+    pub fn src_rect_in(&self, tileset: &Tileset) -> (::bevy::math::Vec2, ::bevy::math::Vec2) {
+        let tileset_size = tileset.dimensions_px.as_f32();
+        let min = self.src_px.as_f32();
+        let max = min + ::bevy::math::Vec2::splat(tileset.grid_size as f32);
+
+        (min / tileset_size, max / tileset_size)
+    }
+
+    /// The column and row of this tile in `tileset`, rows are counted from the top of the image
+    pub fn src_cell(&self, tileset: &Tileset) -> IVec2 {
+        let stride = (tileset.grid_size + tileset.spacing) as i32;
+
+        (self.src_px - IVec2::splat(tileset.padding as i32)) / stride
+    }
+
+    /// The index of this tile in a texture atlas of `tileset`, counted row by row from the top
+    pub fn atlas_index(&self, tileset: &Tileset) -> usize {
+        let cell = self.src_cell(tileset);
+
+        (cell.y * tileset.dimensions_cell.x + cell.x) as usize
+    }
+
+    fn load(tile: &ldtk2::TileInstance, layer_dimensions_px: IVec2) -> LdtkResult<Self> {
+        // Only the lowest two bits are defined by LDTK, anything above is ignored
+        let flip_x = tile.f & 0x1 == 0x1;
         let flip_y = tile.f & 0x2 == 0x2;
 
         let position_px = ::bevy::math::IVec2::new(
@@ -163,6 +967,7 @@ impl Tile {
         );
         let src_px = ::bevy::math::IVec2::new(tile.src[0] as i32, tile.src[1] as i32);
         let id = tile.t;
+        let alpha = 1.0;
 
         Ok(Tile {
             flip_x,
@@ -170,171 +975,1202 @@ impl Tile {
             position_px,
             src_px,
             id,
+            alpha,
         })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Tileset {
+    /// The size of a tile in the image, layers may place tiles on a different grid
     pub grid_size: i64,
     pub ident: String,
     pub padding: i64,
+    /// The space between two tiles in pixels
+    pub spacing: i64,
     pub dimensions_cell: ::bevy::math::IVec2,
+    pub dimensions_px: ::bevy::math::IVec2,
     pub rel_path: String,
     pub id: i64,
+    pub cached_pixel_data: Option<CachedPixelData>,
+}
+
+/// What LDTK caches about each tile of a tileset image, in the order of tile ids
+///
+/// LDTK never embeds the image itself, only these summaries of it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CachedPixelData {
+    pub opaque_tiles: Vec<bool>,
+    pub average_colors: Vec<bevy::render::color::Color>,
+}
+
+impl CachedPixelData {
+    fn load(data: &std::collections::HashMap<String, Option<serde_json::Value>>) -> Option<Self> {
+        let field = |key: &str| data.get(key)?.as_ref()?.as_str();
+
+        let opaque_tiles = field("opaqueTiles")?.chars().map(|c| c == '1').collect();
+        // Every average color is written as four hex digits, ARGB
+        let average_colors = field("averageColors")?
+            .as_bytes()
+            .chunks(4)
+            .map(|argb| {
+                let channel = |index: usize| {
+                    let digit = (argb.get(index)? as char).to_digit(16)?;
+                    Some(digit as f32 / 15.0)
+                };
+
+                Some(bevy::render::color::Color::rgba(
+                    channel(1)?,
+                    channel(2)?,
+                    channel(3)?,
+                    channel(0)?,
+                ))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(CachedPixelData {
+            opaque_tiles,
+            average_colors,
+        })
+    }
+}
+
+impl Tileset {
+    /// The data LDTK cached about the tiles of this tileset, if the editor has computed it
+    pub fn embedded_pixel_data(&self) -> Option<&CachedPixelData> {
+        self.cached_pixel_data.as_ref()
+    }
+
+    fn load(tileset: &ldtk2::TilesetDefinition) -> LdtkResult<Self> {
+        let grid_size = tileset.tile_grid_size;
+        let ident = tileset.identifier.clone();
+        let padding = tileset.padding;
+        let spacing = tileset.spacing;
+        let dimensions_cell = IVec2::new(tileset.c_wid as i32, tileset.c_hei as i32);
+        let dimensions_px = IVec2::new(tileset.px_wid as i32, tileset.px_hei as i32);
+        let rel_path = tileset.rel_path.clone();
+        let id = tileset.uid;
+        let cached_pixel_data = tileset
+            .cached_pixel_data
+            .as_ref()
+            .and_then(CachedPixelData::load);
+
+        Ok(Tileset {
+            grid_size,
+            ident,
+            padding,
+            spacing,
+            dimensions_cell,
+            dimensions_px,
+            rel_path,
+            id,
+            cached_pixel_data,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct LayerDefinition {
+    pub id: i64,
+    /// Where tiles are aligned within their cell, y up
+    pub tile_pivot: ::bevy::math::Vec2,
+    /// The tileset used by the auto-layer rules, if any
+    pub auto_tileset_uid: Option<i64>,
+    pub auto_rule_groups: Vec<AutoRuleGroup>,
+    pub special: SpecialLayerDefinitions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoRuleGroup {
+    pub uid: i64,
+    pub name: String,
+    pub active: bool,
+}
+
+impl LayerDefinition {
+    pub fn has_auto_tileset(&self) -> bool {
+        self.auto_tileset_uid.is_some()
+    }
+
+    fn load(layer_definition: &ldtk2::LayerDefinition) -> LdtkResult<Self> {
+        let id = layer_definition.uid;
+        let tile_pivot = ::bevy::math::Vec2::new(
+            layer_definition.tile_pivot_x as f32,
+            1.0 - layer_definition.tile_pivot_y as f32,
+        );
+        let auto_tileset_uid = layer_definition.auto_tileset_def_uid;
+        let auto_rule_groups = layer_definition
+            .auto_rule_groups
+            .iter()
+            .map(|group| AutoRuleGroup {
+                uid: group.uid,
+                name: group.name.clone(),
+                active: group.active,
+            })
+            .collect();
+        let special = match layer_definition.purple_type {
+            ldtk2::Type::AutoLayer => SpecialLayerDefinitions::AutoLayer,
+            ldtk2::Type::Entities => SpecialLayerDefinitions::Entities,
+            ldtk2::Type::IntGrid => SpecialLayerDefinitions::IntGrid {
+                value_definitions: layer_definition
+                    .int_grid_values
+                    .iter()
+                    .map(|def| {
+                        Ok(IntGridValueDefinition {
+                            color: parse_color(&def.color)?,
+                            identifier: def.identifier.clone(),
+                            value: def.value,
+                        })
+                    })
+                    .collect::<LdtkResult<_>>()?,
+            },
+            ldtk2::Type::Tiles => SpecialLayerDefinitions::Tiles,
+        };
+
+        Ok(LayerDefinition {
+            id,
+            tile_pivot,
+            auto_tileset_uid,
+            auto_rule_groups,
+            special,
+        })
+    }
+}
+
+/// The kind of a layer definition, together with the settings only that kind has
+///
+/// This is `#[non_exhaustive]` so that layer kinds of newer LDTK versions can be added without a
+/// breaking release.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SpecialLayerDefinitions {
+    IntGrid {
+        value_definitions: Vec<IntGridValueDefinition>,
+    },
+    Entities,
+    Tiles,
+    AutoLayer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntGridValueDefinition {
+    pub color: bevy::render::color::Color,
+    /// Unique String identifier
+    pub identifier: Option<String>,
+    /// The IntGrid value itself
+    pub value: i64,
+}
+
+impl IntGridValueDefinition {
+    /// Borrows this definition as a value that can be copied around freely
+    pub fn as_ref(&self) -> IntGridValueRef<'_> {
+        IntGridValueRef {
+            color: self.color,
+            identifier: self.identifier.as_deref(),
+            value: self.value,
+        }
+    }
+}
+
+/// A borrowed [`IntGridValueDefinition`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntGridValueRef<'a> {
+    pub color: bevy::render::color::Color,
+    pub identifier: Option<&'a str>,
+    pub value: i64,
+}
+
+#[derive(Debug)]
+pub struct Level<
+    LevelFields: DeserializeLdtkFields,
+    Entities: DeserializeLdtkEntities,
+    Layers: DeserializeLDtkLayers<Entities = Entities>,
+> {
+    /// The background color, which is also set when there is a background image
+    ///
+    /// This is the color shown in the editor, levels without their own color use the project
+    /// default.
+    pub background_color: ::bevy::render::color::Color,
+    pub background_position_px: Option<::bevy::math::IVec2>,
+    pub background_image_path: Option<String>,
+    pub identifier: String,
+    pub dimensions_px: ::bevy::math::IVec2,
+    pub id: i64,
+    pub world_position_px: ::bevy::math::IVec2,
+    /// The world grid cell of the top left corner, only set for GridVania layouts
+    ///
+    /// Cells count upwards like the other positions, so the cell `(x, y)` shown by LDTK is
+    /// `(x, -y)` here.
+    pub world_grid_position: Option<::bevy::math::IVec2>,
+
+    pub fields: LevelFields,
+    pub layers: Layers,
+
+    _entities: PhantomData<Entities>,
+}
+
+impl<
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > Level<LevelFields, Entities, Layers>
+{
+    /// The color to clear the screen with when showing this level
+    ///
+    /// This is the background color, a background image is drawn on top of it and may not cover
+    /// the whole level.
+    pub fn clear_color(&self) -> ::bevy::render::color::Color {
+        self.background_color
+    }
+
+    /// Returns the layer with the given identifier, or `None` if there is no such layer
+    pub fn layer(&self, identifier: &str) -> Option<&Layer<Entities>> {
+        self.layers.layer(identifier)
+    }
+
+    /// The lightweight metadata of this level, to be added to an ECS entity
+    pub fn component(&self) -> LevelComponent {
+        LevelComponent {
+            identifier: self.identifier.clone(),
+            id: self.id,
+            world_position_px: self.world_position_px,
+            dimensions_px: self.dimensions_px,
+        }
+    }
+
+    /// The size of this level in cells of `grid_size` pixels, partial cells are left out
+    ///
+    /// Layers can have their own grid size, the cells of a single layer are in
+    /// [`Layer::dimensions_cell`].
+    pub fn dimensions_cell(&self, grid_size: i64) -> IVec2 {
+        self.dimensions_px / grid_size as i32
+    }
+
+    /// The bottom left corner of `layer` in world pixels
+    pub fn layer_origin_px(&self, layer: &Layer<Entities>) -> IVec2 {
+        self.world_position_px
+            + IVec2::new(
+                layer.total_offset_px.x,
+                self.dimensions_px.y + layer.total_offset_px.y,
+            )
+    }
+
+    pub fn load(ldtk_level: &ldtk2::Level, world_grid_size: Option<IVec2>) -> LdtkResult<Self> {
+        let dimensions_px = IVec2::new(ldtk_level.px_wid as i32, ldtk_level.px_hei as i32);
+        let world_position_px = level_world_position_px(ldtk_level, dimensions_px);
+        let level_top_left_px = world_position_px + IVec2::new(0, dimensions_px.y);
+        let world_grid_position = world_grid_size.map(|grid_size| {
+            // Divide the y down position like LDTK does, then flip the cell
+            IVec2::new(
+                level_top_left_px.x.div_euclid(grid_size.x),
+                -(-level_top_left_px.y).div_euclid(grid_size.y),
+            )
+        });
+
+        let fields = LevelFields::deserialize_ldtk(&ldtk_level.field_instances)?;
+        let layer_instances = ldtk_level
+            .layer_instances
+            .as_ref()
+            .ok_or_else(|| LdtkError::MissingExternalLevel(ldtk_level.identifier.clone()))?;
+        let layers = Layers::deserialize_ldtk(layer_instances, level_top_left_px).map_err(
+            |err| match err {
+                LdtkError::MissingLayer(_) if layer_instances.is_empty() => {
+                    LdtkError::LevelWithoutLayers(ldtk_level.identifier.clone())
+                }
+                err => err,
+            },
+        )?;
+
+        // `__bgColor` is already resolved by LDTK, falling back to the project default
+        let background_color = parse_color(&ldtk_level.bg_color)?;
+        let background_position_px = ldtk_level
+            .bg_pos
+            .as_ref()
+            .map(|pos| IVec2::new(pos.top_left_px[0] as i32, pos.top_left_px[0] as i32));
+
+        let background_image_path = ldtk_level.bg_rel_path.clone();
+        let identifier = ldtk_level.identifier.clone();
+        let id = ldtk_level.uid;
+
+        Ok(Level {
+            fields,
+            layers,
+            background_color,
+            background_position_px,
+            background_image_path,
+            identifier,
+            dimensions_px,
+            id,
+            world_position_px,
+            world_grid_position,
+            _entities: PhantomData,
+        })
+    }
+
+    /// Loads a level stored in a separate `.ldtkl` file
+    pub fn load_from_path(
+        path: impl AsRef<std::path::Path>,
+        world_grid_size: Option<IVec2>,
+    ) -> LdtkResult<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let ldtk_level: ldtk2::Level = serde_json::from_reader(file)?;
+
+        Self::load(&ldtk_level, world_grid_size)
+    }
+}
+
+/// The identifier of the level the game is currently in, used by the generated `ProjectParam`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CurrentLevel(pub String);
+
+/// Describes a level spawned as an ECS entity, the layers stay in the `World`
+///
+/// LDTK 0.9 has no level iids, so levels are matched by their uid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelComponent {
+    pub identifier: String,
+    pub id: i64,
+    pub world_position_px: IVec2,
+    pub dimensions_px: IVec2,
+}
+
+/// Blends the RGBA8 pixel `source`, scaled by `alpha`, over `target`
+#[cfg(feature = "rasterize")]
+fn blend_over(target: &mut [u8], source: &[u8], alpha: f32) {
+    let source_alpha = source[3] as f32 / 255.0 * alpha;
+    let target_alpha = target[3] as f32 / 255.0;
+    let out_alpha = source_alpha + target_alpha * (1.0 - source_alpha);
+
+    if out_alpha <= 0.0 {
+        return;
+    }
+
+    for channel in 0..3 {
+        let blended = (source[channel] as f32 * source_alpha
+            + target[channel] as f32 * target_alpha * (1.0 - source_alpha))
+            / out_alpha;
+        target[channel] = blended.round() as u8;
+    }
+    target[3] = (out_alpha * 255.0).round() as u8;
+}
+
+#[derive(Debug)]
+pub struct Layer<EntityFields> {
+    pub dimensions_cell: IVec2,
+    /// The size of a cell when placing tiles, which can differ from [`Tileset::grid_size`]
+    pub grid_size: i64,
+    pub opacity: f64,
+    /// The bottom left corner of this layer relative to the top left corner of its level, y up
+    ///
+    /// This is `(pxTotalOffsetX, -pxTotalOffsetY - c_hei * grid_size)`, so unlike the other
+    /// offsets it includes the height of the layer. [`Level::layer_origin_px`] turns it into world
+    /// pixels.
+    pub total_offset_px: ::bevy::math::IVec2,
+    /// The offset set on the layer definition, y up
+    pub definition_offset_px: ::bevy::math::IVec2,
+    /// The offset set on this layer instance, y up
+    pub instance_offset_px: ::bevy::math::IVec2,
+    pub visible: bool,
+    /// The position in the draw order, the top-most layer has the largest order
+    pub order: i32,
+    /// The random seed used by auto-layer rules
+    pub seed: i64,
+    pub tileset_uid: Option<i64>,
+    pub layer_definition: i64,
+
+    pub special: SpecialValues<EntityFields>,
+}
+
+/// A tile placed on a cell, in the shape tilemap crates such as `bevy_ecs_tilemap` expect
+///
+/// This crate does not depend on any of them, so that their Bevy version does not have to match.
+/// Copy the fields over into the tile type of the tilemap crate in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TilemapTile {
+    /// The cell of the tile, counting from the bottom left
+    pub position: ::bevy::math::UVec2,
+    pub texture_index: u16,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Tile {
+    /// Converts this tile into a tilemap tile placed at its cell, counting from the bottom left
+    pub fn to_tilemap_tile(&self, grid_size: i64) -> TilemapTile {
+        // `position_px` is the top left pixel of the tile, y up
+        let grid_size = grid_size as i32;
+        let position_cell = IVec2::new(
+            self.position_px.x / grid_size,
+            (self.position_px.y + 1) / grid_size - 1,
+        );
+
+        TilemapTile {
+            position: ::bevy::math::UVec2::new(position_cell.x as u32, position_cell.y as u32),
+            texture_index: self.id as u16,
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+        }
+    }
+}
+
+impl<EntityFields> Layer<EntityFields> {
+    /// Converts all tiles of this layer, or returns `None` if it is an entities layer
+    ///
+    /// Hidden layers also return `None`, unless `include_hidden` is set.
+    pub fn tilemap_tiles(&self, include_hidden: bool) -> Option<Vec<TilemapTile>> {
+        let tiles = self.rendered_tiles(include_hidden)?;
+
+        Some(
+            tiles
+                .iter()
+                .map(|tile| tile.to_tilemap_tile(self.grid_size))
+                .collect(),
+        )
+    }
+}
+
+/// Toggles the debug overlay, it is enabled by default
+#[cfg(feature = "debug_draw")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LdtkDebugDraw {
+    pub enabled: bool,
+}
+
+#[cfg(feature = "debug_draw")]
+impl Default for LdtkDebugDraw {
+    fn default() -> Self {
+        LdtkDebugDraw { enabled: true }
+    }
+}
+
+/// A shape of the debug overlay, in world pixels with y up
+#[cfg(feature = "debug_draw")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugShape {
+    /// The outline of an entity or layer
+    Rect {
+        min: ::bevy::math::Vec2,
+        max: ::bevy::math::Vec2,
+        color: ::bevy::render::color::Color,
+    },
+    /// The pivot of an entity
+    Point {
+        position: ::bevy::math::Vec2,
+        color: ::bevy::render::color::Color,
+    },
+}
+
+#[cfg(feature = "debug_draw")]
+impl<
+        WorldType: TypeUuid,
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > World<WorldType, LevelFields, Entities, Layers>
+{
+    /// Collects the outlines of all layers and entities, and the pivots of all entities
+    ///
+    /// The pinned Bevy version has no immediate mode drawing, so these are meant to be handed to
+    /// whatever line renderer the game uses. Nothing is returned while `debug` is disabled.
+    pub fn debug_shapes(&self, debug: &LdtkDebugDraw) -> Vec<DebugShape> {
+        use ::bevy::render::color::Color;
+
+        if !debug.enabled {
+            return vec![];
+        }
+
+        let mut shapes = vec![];
+
+        for level in &self.levels {
+            for layer in level.layers.layers() {
+                let size = layer.dimensions_cell * layer.grid_size as i32;
+                let layer_origin = level.layer_origin_px(layer);
+
+                shapes.push(DebugShape::Rect {
+                    min: layer_origin.as_f32(),
+                    max: (layer_origin + size).as_f32(),
+                    color: Color::GRAY,
+                });
+
+                let entities = match &layer.special {
+                    SpecialValues::Entities(entities) => entities,
+                    _ => continue,
+                };
+
+                for entity in entities.bounds() {
+                    let position = (layer_origin + entity.position_px).as_f32();
+                    let dimensions = entity.dimensions_px.as_f32();
+                    let min = position - entity.pivot * dimensions;
+
+                    shapes.push(DebugShape::Rect {
+                        min,
+                        max: min + dimensions,
+                        color: Color::GREEN,
+                    });
+                    shapes.push(DebugShape::Point {
+                        position,
+                        color: Color::RED,
+                    });
+                }
+            }
+        }
+
+        shapes
+    }
+}
+
+#[cfg(feature = "test_util")]
+impl<
+        WorldType: TypeUuid,
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > World<WorldType, LevelFields, Entities, Layers>
+{
+    /// Builds a world by hand, for testing code that works with loaded worlds
+    pub fn new(
+        levels: Vec<Level<LevelFields, Entities, Layers>>,
+        tilesets: Vec<Tileset>,
+        layer_definitions: Vec<LayerDefinition>,
+    ) -> Self {
+        let entity_counts = count_entities(&levels);
+        let tileset_identifiers = tilesets
+            .iter()
+            .map(|tileset| (tileset.ident.clone(), tileset.id))
+            .collect();
+
+        World {
+            levels,
+            tilesets: tilesets
+                .into_iter()
+                .map(|tileset| (tileset.id, tileset))
+                .collect(),
+            layer_definitions: layer_definitions
+                .into_iter()
+                .map(|def| (def.id, def))
+                .collect(),
+            tileset_identifiers,
+            world_grid_size: None,
+            entity_counts,
+            json_version: String::new(),
+            #[cfg(feature = "keep_raw")]
+            raw: serde_json::Value::Null,
+            _entities: PhantomData,
+            _world_type: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "test_util")]
+impl<
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > Level<LevelFields, Entities, Layers>
+{
+    /// Builds a level by hand with a black background, the position is its bottom left corner
+    pub fn new(
+        identifier: &str,
+        id: i64,
+        dimensions_px: IVec2,
+        world_position_px: IVec2,
+        fields: LevelFields,
+        layers: Layers,
+    ) -> Self {
+        Level {
+            background_color: ::bevy::render::color::Color::BLACK,
+            background_position_px: None,
+            background_image_path: None,
+            identifier: identifier.to_string(),
+            dimensions_px,
+            id,
+            world_position_px,
+            world_grid_position: None,
+            fields,
+            layers,
+            _entities: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "test_util")]
+impl<EntityFields> Layer<EntityFields> {
+    fn new(
+        dimensions_cell: IVec2,
+        grid_size: i64,
+        layer_definition: i64,
+        special: SpecialValues<EntityFields>,
+    ) -> Self {
+        Layer {
+            dimensions_cell,
+            grid_size,
+            opacity: 1.0,
+            total_offset_px: IVec2::new(0, -dimensions_cell.y * grid_size as i32),
+            definition_offset_px: IVec2::ZERO,
+            instance_offset_px: IVec2::ZERO,
+            visible: true,
+            order: 0,
+            seed: 0,
+            tileset_uid: None,
+            layer_definition,
+            special,
+        }
+    }
+
+    /// Builds a tile layer by hand, drawn with the tileset `tileset_uid`
+    pub fn new_tiles(
+        dimensions_cell: IVec2,
+        grid_size: i64,
+        layer_definition: i64,
+        tileset_uid: i64,
+        tiles: Vec<Tile>,
+    ) -> Self {
+        let special = SpecialValues::Tiles {
+            tileset: Some(tileset_uid),
+            tiles,
+        };
+
+        Layer {
+            tileset_uid: Some(tileset_uid),
+            ..Self::new(dimensions_cell, grid_size, layer_definition, special)
+        }
+    }
+
+    /// Builds an IntGrid layer by hand, `values` start with the bottom row
+    pub fn new_int_grid(
+        dimensions_cell: IVec2,
+        grid_size: i64,
+        layer_definition: i64,
+        values: Vec<i64>,
+    ) -> Self {
+        let values = IntGrid {
+            values,
+            width: dimensions_cell.x,
+            height: dimensions_cell.y,
+        };
+        let special = SpecialValues::IntGrid {
+            values,
+            auto_layer: vec![],
+        };
+
+        Self::new(dimensions_cell, grid_size, layer_definition, special)
+    }
+
+    /// Builds an entity layer by hand
+    pub fn new_entities(
+        dimensions_cell: IVec2,
+        grid_size: i64,
+        layer_definition: i64,
+        entities: EntityFields,
+    ) -> Self {
+        let special = SpecialValues::Entities(entities);
+
+        Self::new(dimensions_cell, grid_size, layer_definition, special)
+    }
+}
+
+/// A flattened, serializable copy of a world for exporting to other tools
+///
+/// All positions are in world pixels, y up.
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SceneDescription {
+    pub tilesets: Vec<SceneTileset>,
+    pub levels: Vec<SceneLevel>,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SceneTileset {
+    pub uid: i64,
+    pub identifier: String,
+    pub rel_path: String,
+    pub grid_size: i64,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SceneLevel {
+    pub identifier: String,
+    /// The bottom left corner
+    pub position_px: [i32; 2],
+    pub dimensions_px: [i32; 2],
+    /// The layers from the bottom-most to the top-most
+    pub layers: Vec<SceneLayer>,
 }
 
-impl Tileset {
-    fn load(tileset: &ldtk2::TilesetDefinition) -> LdtkResult<Self> {
-        let grid_size = tileset.tile_grid_size;
-        let ident = tileset.identifier.clone();
-        let padding = tileset.padding;
-        let dimensions_cell = IVec2::new(tileset.c_wid as i32, tileset.c_hei as i32);
-        let rel_path = tileset.rel_path.clone();
-        let id = tileset.uid;
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SceneLayer {
+    /// The bottom left corner
+    pub position_px: [i32; 2],
+    pub grid_size: i64,
+    pub opacity: f64,
+    pub visible: bool,
+    pub tileset_uid: Option<i64>,
+    pub tiles: Vec<SceneTile>,
+    pub entities: Vec<SceneEntity>,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct SceneTile {
+    /// The top left corner
+    pub position_px: [i32; 2],
+    /// The top left corner in the tileset image, y down
+    pub src_px: [i32; 2],
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SceneEntity {
+    pub identifier: &'static str,
+    /// The position of the pivot
+    pub position_px: [i32; 2],
+    pub dimensions_px: [i32; 2],
+    pub pivot: [f32; 2],
+}
+
+#[cfg(feature = "serialize")]
+impl<
+        WorldType: TypeUuid,
+        LevelFields: DeserializeLdtkFields,
+        Entities: DeserializeLdtkEntities,
+        Layers: DeserializeLDtkLayers<Entities = Entities>,
+    > World<WorldType, LevelFields, Entities, Layers>
+{
+    /// Flattens this world into plain data with world space positions
+    pub fn to_scene(&self) -> SceneDescription {
+        let mut tilesets = self
+            .tilesets
+            .values()
+            .map(|tileset| SceneTileset {
+                uid: tileset.id,
+                identifier: tileset.ident.clone(),
+                rel_path: tileset.rel_path.clone(),
+                grid_size: tileset.grid_size,
+            })
+            .collect::<Vec<_>>();
+        tilesets.sort_by_key(|tileset| tileset.uid);
+
+        let levels = self
+            .levels
+            .iter()
+            .map(|level| {
+                let mut layers = level.layers.layers();
+                layers.sort_by_key(|layer| layer.order);
+
+                let layers = layers
+                    .into_iter()
+                    .map(|layer| {
+                        let origin = level.layer_origin_px(layer);
+
+                        let tiles = layer
+                            .tiles()
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|tile| SceneTile {
+                                position_px: (origin + tile.position_px).into(),
+                                src_px: tile.src_px.into(),
+                                flip_x: tile.flip_x,
+                                flip_y: tile.flip_y,
+                            })
+                            .collect();
+
+                        let entities = match &layer.special {
+                            SpecialValues::Entities(entities) => entities
+                                .bounds()
+                                .into_iter()
+                                .map(|entity| SceneEntity {
+                                    identifier: entity.identifier,
+                                    position_px: (origin + entity.position_px).into(),
+                                    dimensions_px: entity.dimensions_px.into(),
+                                    pivot: entity.pivot.into(),
+                                })
+                                .collect(),
+                            _ => vec![],
+                        };
+
+                        SceneLayer {
+                            position_px: origin.into(),
+                            grid_size: layer.grid_size,
+                            opacity: layer.opacity,
+                            visible: layer.visible,
+                            tileset_uid: layer.tileset_uid,
+                            tiles,
+                            entities,
+                        }
+                    })
+                    .collect();
+
+                SceneLevel {
+                    identifier: level.identifier.clone(),
+                    position_px: level.world_position_px.into(),
+                    dimensions_px: level.dimensions_px.into(),
+                    layers,
+                }
+            })
+            .collect();
+
+        SceneDescription { tilesets, levels }
+    }
+}
+
+/// Vertex data for drawing a whole tile layer as a single mesh
+///
+/// Every tile is a quad of four vertices and two triangles. Positions are relative to the bottom
+/// left of the layer, y up, and the UVs are normalized to the tileset texture.
+#[derive(Debug, Clone, Default)]
+pub struct TileMeshData {
+    pub positions: Vec<[f32; 3]>,
+    pub uvs: Vec<[f32; 2]>,
+    pub colors: Vec<[f32; 4]>,
+    pub indices: Vec<u32>,
+}
+
+impl<EntityFields> Layer<EntityFields> {
+    /// The total offset as LDTK stores it, y down, for recomputing it in other coordinate systems
+    pub fn ldtk_total_offset_px(&self) -> IVec2 {
+        let height_px = self.dimensions_cell.y * self.grid_size as i32;
+
+        IVec2::new(
+            self.total_offset_px.x,
+            -(self.total_offset_px.y + height_px),
+        )
+    }
+
+    /// Returns the tiles of this layer, or `None` if it is an entities layer
+    pub fn tiles(&self) -> Option<&[Tile]> {
+        match &self.special {
+            SpecialValues::IntGrid { auto_layer, .. } | SpecialValues::AutoLayer { auto_layer } => {
+                Some(auto_layer)
+            }
+            SpecialValues::Tiles { tiles, .. } => Some(tiles),
+            SpecialValues::Entities(_) => None,
+        }
+    }
+
+    /// Returns the values of this layer, or `None` if it is not an IntGrid layer
+    pub fn as_int_grid(&self) -> Option<&IntGrid> {
+        match &self.special {
+            SpecialValues::IntGrid { values, .. } => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the tiles in the order LDTK stores them, or `None` if it is an entities layer
+    ///
+    /// `tiles` lists them starting from the bottom instead, positions are y up either way.
+    pub fn native_tiles(&self) -> Option<Vec<Tile>> {
+        let tiles = self.tiles()?.to_vec();
+
+        Some(restore_row_wise(tiles, self.dimensions_cell.x as usize))
+    }
+
+    /// Returns the hand placed tiles of this layer, or `None` if it is not a tile layer
+    pub fn as_tiles(&self) -> Option<&[Tile]> {
+        match &self.special {
+            SpecialValues::Tiles { tiles, .. } => Some(tiles),
+            _ => None,
+        }
+    }
+
+    /// Returns the entities of this layer, or `None` if it is not an entity layer
+    pub fn as_entities(&self) -> Option<&EntityFields> {
+        match &self.special {
+            SpecialValues::Entities(entities) => Some(entities),
+            _ => None,
+        }
+    }
+
+    /// Returns the auto-layer tiles of an auto or IntGrid layer, or `None` for other layers
+    pub fn as_auto_layer(&self) -> Option<&[Tile]> {
+        match &self.special {
+            SpecialValues::IntGrid { auto_layer, .. } | SpecialValues::AutoLayer { auto_layer } => {
+                Some(auto_layer)
+            }
+            _ => None,
+        }
+    }
+
+    /// The tiles to render, which are none for hidden layers unless `include_hidden` is set
+    fn rendered_tiles(&self, include_hidden: bool) -> Option<&[Tile]> {
+        if !self.visible && !include_hidden {
+            return None;
+        }
+
+        self.tiles()
+    }
+
+    /// Builds the vertex data of all tiles in this layer, using `tileset` for the UVs
+    ///
+    /// Flipped tiles have their UVs swapped and the effective alpha is stored in the vertex colors.
+    /// Hidden layers return `None`, unless `include_hidden` is set.
+    pub fn tile_mesh_data(&self, tileset: &Tileset, include_hidden: bool) -> Option<TileMeshData> {
+        let tiles = self.rendered_tiles(include_hidden)?;
+        let tile_size = self.grid_size as f32;
+
+        let mut data = TileMeshData::default();
+
+        for tile in tiles {
+            let left = tile.position_px.x as f32;
+            let top = tile.position_px.y as f32 + 1.0;
+            let (right, bottom) = (left + tile_size, top - tile_size);
+
+            let (src_min, src_max) = tile.src_rect_in(tileset);
+            let mut u = [src_min.x, src_max.x];
+            let mut v = [src_min.y, src_max.y];
+            if tile.flip_x {
+                u.swap(0, 1);
+            }
+            if tile.flip_y {
+                v.swap(0, 1);
+            }
+
+            let index = data.positions.len() as u32;
+            data.positions.extend_from_slice(&[
+                [left, bottom, 0.0],
+                [right, bottom, 0.0],
+                [right, top, 0.0],
+                [left, top, 0.0],
+            ]);
+            data.uvs
+                .extend_from_slice(&[[u[0], v[1]], [u[1], v[1]], [u[1], v[0]], [u[0], v[0]]]);
+            data.colors
+                .extend_from_slice(&[[1.0, 1.0, 1.0, tile.effective_alpha(self)]; 4]);
+            data.indices.extend_from_slice(&[
+                index,
+                index + 1,
+                index + 2,
+                index,
+                index + 2,
+                index + 3,
+            ]);
+        }
+
+        Some(data)
+    }
+
+    /// Draws all tiles of this layer into an RGBA8 buffer the size of the layer, rows from the top
+    ///
+    /// `tileset_pixels` are the decoded RGBA8 pixels of `tileset`'s image. Tiles are drawn in order
+    /// and blended over each other, respecting flips and the layer opacity. Source pixels outside
+    /// of the image are skipped. Entity layers and hidden layers return `None`, unless
+    /// `include_hidden` is set for the latter.
+    #[cfg(feature = "rasterize")]
+    pub fn rasterize(
+        &self,
+        tileset_pixels: &[u8],
+        tileset: &Tileset,
+        include_hidden: bool,
+    ) -> Option<Vec<u8>> {
+        let tiles = self.rendered_tiles(include_hidden)?;
+
+        let width = (self.dimensions_cell.x as i64 * self.grid_size) as usize;
+        let height = (self.dimensions_cell.y as i64 * self.grid_size) as usize;
+        let mut output = vec![0; width * height * 4];
+
+        let source_width = tileset.dimensions_px.x as i64;
+        let source_height = tileset.dimensions_px.y as i64;
+        let tile_size = tileset.grid_size;
 
-        Ok(Tileset {
-            grid_size,
-            ident,
-            padding,
-            dimensions_cell,
-            rel_path,
-            id,
-        })
+        for tile in tiles {
+            let alpha = tile.effective_alpha(self);
+            // `position_px` is y up, the buffer is y down like the LDTK file
+            let left = tile.position_px.x as i64;
+            let top = height as i64 - 1 - tile.position_px.y as i64;
+
+            for y in 0..tile_size {
+                for x in 0..tile_size {
+                    let (target_x, target_y) = (left + x, top + y);
+                    if target_x < 0
+                        || target_y < 0
+                        || target_x >= width as i64
+                        || target_y >= height as i64
+                    {
+                        continue;
+                    }
+
+                    let source_x =
+                        tile.src_px.x as i64 + if tile.flip_x { tile_size - 1 - x } else { x };
+                    let source_y =
+                        tile.src_px.y as i64 + if tile.flip_y { tile_size - 1 - y } else { y };
+                    if source_x >= source_width || source_y >= source_height {
+                        continue;
+                    }
+
+                    let source = ((source_y * source_width + source_x) * 4) as usize;
+                    let source = match tileset_pixels.get(source..source + 4) {
+                        Some(source) => source,
+                        None => continue,
+                    };
+                    let target = ((target_y * width as i64 + target_x) * 4) as usize;
+
+                    blend_over(&mut output[target..target + 4], source, alpha);
+                }
+            }
+        }
+
+        Some(output)
     }
-}
 
-#[derive(Debug)]
-pub struct LayerDefinition {
-    pub id: i64,
-    pub special: SpecialLayerDefinitions,
-}
+    /// Whether this layer has any tiles placed by auto-layer rules
+    pub fn has_auto_tiles(&self) -> bool {
+        match &self.special {
+            SpecialValues::IntGrid { auto_layer, .. } | SpecialValues::AutoLayer { auto_layer } => {
+                !auto_layer.is_empty()
+            }
+            _ => false,
+        }
+    }
 
-impl LayerDefinition {
-    fn load(layer_definition: &ldtk2::LayerDefinition) -> LdtkResult<Self> {
-        let id = layer_definition.uid;
-        let special = match layer_definition.purple_type {
-            ldtk2::Type::AutoLayer => SpecialLayerDefinitions::AutoLayer,
-            ldtk2::Type::Entities => SpecialLayerDefinitions::Entities,
-            ldtk2::Type::IntGrid => SpecialLayerDefinitions::IntGrid {
-                value_definitions: layer_definition
-                    .int_grid_values
-                    .iter()
-                    .map(|def| IntGridValueDefinition {
-                        color: bevy::prelude::Color::hex(&def.color[1..]).unwrap(),
-                        identifier: def.identifier.clone(),
-                        value: def.value,
-                    })
-                    .collect(),
-            },
-            ldtk2::Type::Tiles => SpecialLayerDefinitions::Tiles,
+    /// Returns the color of the IntGrid cell at `cell`, counting from the bottom left
+    ///
+    /// An empty cell (value `0`) is transparent. Returns `None` if this is not an IntGrid layer,
+    /// if `cell` lies outside of the layer, or if the value has no matching definition.
+    pub fn int_grid_color_at(
+        &self,
+        cell: IVec2,
+        layer_definition: &LayerDefinition,
+    ) -> Option<bevy::render::color::Color> {
+        let values = match &self.special {
+            SpecialValues::IntGrid { values, .. } => values,
+            _ => return None,
+        };
+
+        let value_definitions = match &layer_definition.special {
+            SpecialLayerDefinitions::IntGrid { value_definitions } => value_definitions,
+            _ => return None,
         };
 
-        Ok(LayerDefinition { id, special })
+        let value = values.get(cell.x, cell.y)?;
+
+        if value == 0 {
+            return Some(bevy::render::color::Color::NONE);
+        }
+
+        value_definitions
+            .iter()
+            .find(|def| def.value == value)
+            .map(|def| def.color)
     }
 }
 
-#[derive(Debug)]
-pub enum SpecialLayerDefinitions {
-    IntGrid {
-        value_definitions: Vec<IntGridValueDefinition>,
-    },
-    Entities,
-    Tiles,
-    AutoLayer,
+/// The values of an IntGrid layer, `0` meaning an empty cell
+#[derive(Debug, Clone)]
+pub struct IntGrid {
+    pub values: Vec<i64>,
+    pub width: i32,
+    pub height: i32,
 }
 
-#[derive(Debug)]
-pub struct IntGridValueDefinition {
-    pub color: bevy::render::color::Color,
-    /// Unique String identifier
-    pub identifier: Option<String>,
-    /// The IntGrid value itself
-    pub value: i64,
-}
+impl IntGrid {
+    /// Returns the value at the given cell, counting from the bottom left
+    pub fn get(&self, x: i32, y: i32) -> Option<i64> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
 
-#[derive(Debug)]
-pub struct Level<
-    LevelFields: DeserializeLdtkFields,
-    Entities: DeserializeLdtkEntities,
-    Layers: DeserializeLDtkLayers<Entities = Entities>,
-> {
-    pub background_color: ::bevy::render::color::Color,
-    pub background_position_px: Option<::bevy::math::IVec2>,
-    pub background_image_path: Option<String>,
-    pub identifier: String,
-    pub dimensions_px: ::bevy::math::IVec2,
-    pub id: i64,
-    pub world_position_px: ::bevy::math::IVec2,
+        self.values.get((y * self.width + x) as usize).copied()
+    }
 
-    pub fields: LevelFields,
-    pub layers: Layers,
+    /// The values in the order LDTK stores them, starting with the top row
+    ///
+    /// `values` starts with the bottom row instead, cells are y up either way.
+    pub fn native_values(&self) -> Vec<i64> {
+        restore_row_wise(self.values.clone(), self.width as usize)
+    }
 
-    _entities: PhantomData<Entities>,
-}
+    /// Iterates over every cell and its value, starting at the bottom left
+    pub fn iter_cells(&self) -> impl Iterator<Item = (IVec2, i64)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| IVec2::new(x, y)))
+            .filter_map(move |cell| Some((cell, self.get(cell.x, cell.y)?)))
+    }
 
-impl<
-        LevelFields: DeserializeLdtkFields,
-        Entities: DeserializeLdtkEntities,
-        Layers: DeserializeLDtkLayers<Entities = Entities>,
-    > Level<LevelFields, Entities, Layers>
-{
-    pub fn load(ldtk_level: &ldtk2::Level) -> LdtkResult<Self> {
-        let fields = LevelFields::deserialize_ldtk(&ldtk_level.field_instances)?;
-        // TODO: #1 Load from seperated ldtk files
-        let layers = Layers::deserialize_ldtk(&ldtk_level.layer_instances.as_ref().unwrap())?;
+    /// Iterates over every non-empty cell together with the definition of its value
+    ///
+    /// Values without a definition are skipped, as is everything if `layer_definition` does not
+    /// belong to an IntGrid layer.
+    pub fn iter_defined<'a>(
+        &'a self,
+        layer_definition: &'a LayerDefinition,
+    ) -> impl Iterator<Item = (IVec2, &'a IntGridValueDefinition)> + 'a {
+        let value_definitions: &[IntGridValueDefinition] = match &layer_definition.special {
+            SpecialLayerDefinitions::IntGrid { value_definitions } => value_definitions,
+            _ => &[],
+        };
 
-        let background_color = bevy::prelude::Color::hex(&ldtk_level.bg_color[1..]).unwrap();
-        let background_position_px = ldtk_level
-            .bg_pos
-            .as_ref()
-            .map(|pos| IVec2::new(pos.top_left_px[0] as i32, pos.top_left_px[0] as i32));
+        self.iter_cells()
+            .filter(|(_, value)| *value != 0)
+            .filter_map(move |(cell, value)| {
+                let definition = value_definitions.iter().find(|def| def.value == value)?;
+                Some((cell, definition))
+            })
+    }
 
-        let background_image_path = ldtk_level.bg_rel_path.clone();
-        let identifier = ldtk_level.identifier.clone();
-        let dimensions_px = IVec2::new(ldtk_level.px_wid as i32, ldtk_level.px_hei as i32);
-        let id = ldtk_level.uid;
-        let world_position_px = IVec2::new(
-            ldtk_level.world_x as i32,
-            -ldtk_level.world_y as i32 - dimensions_px.y,
-        );
+    /// Resolves every value to the identifier of its definition, in the same order as `values`
+    ///
+    /// Empty cells, values without a definition or identifier, and everything if
+    /// `layer_definition` does not belong to an IntGrid layer are `None`.
+    pub fn identifiers<'a>(&self, layer_definition: &'a LayerDefinition) -> Vec<Option<&'a str>> {
+        let value_definitions: &[IntGridValueDefinition] = match &layer_definition.special {
+            SpecialLayerDefinitions::IntGrid { value_definitions } => value_definitions,
+            _ => &[],
+        };
 
-        Ok(Level {
-            fields,
-            layers,
-            background_color,
-            background_position_px,
-            background_image_path,
-            identifier,
-            dimensions_px,
-            id,
-            world_position_px,
-            _entities: PhantomData,
-        })
+        self.values
+            .iter()
+            .map(|&value| {
+                value_definitions
+                    .iter()
+                    .filter(|_| value != 0)
+                    .find(|def| def.value == value)?
+                    .identifier
+                    .as_deref()
+            })
+            .collect()
     }
 }
 
-#[derive(Debug)]
-pub struct Layer<EntityFields> {
-    pub dimensions_cell: IVec2,
-    pub grid_size: i64,
-    pub opacity: f64,
-    pub total_offset_px: ::bevy::math::IVec2,
-    pub visible: bool,
-    pub tileset_uid: Option<i64>,
-    pub layer_definition: i64,
-
-    pub special: SpecialValues<EntityFields>,
+/// Parses a `#RRGGBB` color as used throughout LDTK
+fn parse_color(hex: &str) -> LdtkResult<bevy::render::color::Color> {
+    Ok(bevy::render::color::Color::hex(
+        hex.trim_start_matches('#'),
+    )?)
 }
 
+/// Reorders the rows so that the bottom row comes first
 fn reverse_row_wise<T: Clone>(list: Vec<T>, row_length: usize) -> Vec<T> {
     let mut list = list.chunks(row_length).collect::<Vec<_>>();
     list.reverse();
     list.concat()
 }
 
+/// Undoes `reverse_row_wise`, bringing back the order LDTK stores rows in
+///
+/// The shorter row of a list that does not fill its last row was moved to the front, so it is
+/// split off first.
+fn restore_row_wise<T: Clone>(list: Vec<T>, row_length: usize) -> Vec<T> {
+    if row_length == 0 {
+        return list;
+    }
+
+    let (partial, full) = list.split_at(list.len() % row_length);
+    let mut rows = full.chunks(row_length).collect::<Vec<_>>();
+    rows.reverse();
+    rows.push(partial);
+    rows.concat()
+}
+
 impl<EntityFields: DeserializeLdtkEntities> Layer<EntityFields> {
-    pub fn load(ldtk_layer: &ldtk2::LayerInstance) -> LdtkResult<Self> {
+    pub fn load(
+        ldtk_layer: &ldtk2::LayerInstance,
+        level_top_left_px: IVec2,
+        order: i32,
+    ) -> LdtkResult<Self> {
         let dimensions_cell = IVec2::new(ldtk_layer.c_wid as i32, ldtk_layer.c_hei as i32);
         let grid_size = ldtk_layer.grid_size;
         let opacity = ldtk_layer.opacity;
@@ -342,14 +2178,40 @@ impl<EntityFields: DeserializeLdtkEntities> Layer<EntityFields> {
             ldtk_layer.px_total_offset_x as i32,
             -ldtk_layer.px_total_offset_y as i32 - dimensions_cell.y as i32 * grid_size as i32,
         );
+        // The total offset is the sum of the definition and instance offsets
+        let instance_offset_px = IVec2::new(
+            ldtk_layer.px_offset_x as i32,
+            -ldtk_layer.px_offset_y as i32,
+        );
+        let definition_offset_px = IVec2::new(
+            (ldtk_layer.px_total_offset_x - ldtk_layer.px_offset_x) as i32,
+            -(ldtk_layer.px_total_offset_y - ldtk_layer.px_offset_y) as i32,
+        );
         let visible = ldtk_layer.visible;
+        let seed = ldtk_layer.seed;
         let tileset_uid = ldtk_layer.tileset_def_uid;
         let layer_definition = ldtk_layer.layer_def_uid;
 
         let special = match ldtk_layer.layer_instance_type.as_str() {
             "IntGrid" => {
-                let values =
-                    reverse_row_wise(ldtk_layer.int_grid_csv.clone(), ldtk_layer.c_wid as usize);
+                let expected = (ldtk_layer.c_wid * ldtk_layer.c_hei) as usize;
+                let found = ldtk_layer.int_grid_csv.len();
+                if found != expected {
+                    return Err(LdtkError::GridSizeMismatch {
+                        layer: ldtk_layer.identifier.clone(),
+                        expected,
+                        found,
+                    });
+                }
+
+                let values = IntGrid {
+                    values: reverse_row_wise(
+                        ldtk_layer.int_grid_csv.clone(),
+                        ldtk_layer.c_wid as usize,
+                    ),
+                    width: dimensions_cell.x,
+                    height: dimensions_cell.y,
+                };
 
                 let auto_layer = reverse_row_wise(
                     ldtk_layer
@@ -363,16 +2225,28 @@ impl<EntityFields: DeserializeLdtkEntities> Layer<EntityFields> {
                 SpecialValues::IntGrid { values, auto_layer }
             }
             "Entities" => {
-                let entities = EntityFields::deserialize_ldtk(
-                    &ldtk_layer.entity_instances,
-                    dimensions_cell,
-                    dimensions_cell * grid_size as i32,
-                )?;
+                let context = EntityContext {
+                    parent_size_grid: dimensions_cell,
+                    parent_size_px: dimensions_cell * grid_size as i32,
+                    grid_size,
+                    layer_origin_px: level_top_left_px + total_offset_px,
+                    layer_order: order,
+                };
+                let entities =
+                    EntityFields::deserialize_ldtk(&ldtk_layer.entity_instances, &context)?;
 
                 SpecialValues::Entities(entities)
             }
             "Tiles" => {
                 let tileset = ldtk_layer.tileset_def_uid;
+
+                #[cfg(feature = "validate_tilesets")]
+                if tileset.is_none() && !ldtk_layer.grid_tiles.is_empty() {
+                    return Err(LdtkError::TilesWithoutTileset {
+                        layer: ldtk_layer.identifier.clone(),
+                    });
+                }
+
                 let tiles = reverse_row_wise(
                     ldtk_layer
                         .grid_tiles
@@ -405,17 +2279,26 @@ impl<EntityFields: DeserializeLdtkEntities> Layer<EntityFields> {
             grid_size,
             opacity,
             total_offset_px,
+            definition_offset_px,
+            instance_offset_px,
             visible,
+            order,
+            seed,
             tileset_uid,
             layer_definition,
         })
     }
 }
 
+/// The contents of a layer instance, depending on the kind of layer
+///
+/// Tile and AutoLayer layers only hold tiles, IntGrid layers may have auto-layer tiles on top of
+/// their values. Matching on it needs a wildcard arm, as newer LDTK versions add layer kinds.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum SpecialValues<Entities> {
     IntGrid {
-        values: Vec<i64>,
+        values: IntGrid,
         auto_layer: Vec<Tile>,
     },
     Entities(Entities),
@@ -430,27 +2313,421 @@ pub enum SpecialValues<Entities> {
 
 #[doc(hidden)]
 pub mod private {
-    use crate::error::LdtkResult;
+    use crate::error::{LdtkError, LdtkResult};
     use serde::de::DeserializeOwned;
 
     // Re-exports for the derive crate
     pub use bevy::reflect::TypeUuid;
     pub use bevy::reflect::Uuid;
+    #[cfg(feature = "aseprite")]
     pub use bevy_spicy_aseprite::aseprite;
     pub use ldtk2;
     pub use serde::Deserialize;
+    pub use serde_json;
 
-    pub fn parse_field<T: DeserializeOwned + 'static>(field: &serde_json::Value) -> LdtkResult<T> {
-        if std::any::TypeId::of::<T>() == std::any::TypeId::of::<bevy::render::color::Color>() {
-            let hex: String = serde_json::from_value(field.clone())?;
+    pub fn raw_editor_values(instances: &[ldtk2::FieldInstance]) -> crate::RawEditorValues {
+        instances
+            .iter()
+            .map(|field| (field.identifier.clone(), field.real_editor_values.clone()))
+            .collect()
+    }
+
+    pub fn entity_tile(entity: &ldtk2::EntityInstance) -> Option<crate::TileRect> {
+        entity.tile.as_ref().map(|tile| crate::TileRect {
+            tileset_uid: tile.tileset_uid,
+            x: tile.src_rect[0],
+            y: tile.src_rect[1],
+            w: tile.src_rect[2],
+            h: tile.src_rect[3],
+        })
+    }
+
+    pub trait NumericField {
+        fn numeric_values(&self) -> Vec<f64>;
+    }
+
+    impl NumericField for i64 {
+        fn numeric_values(&self) -> Vec<f64> {
+            vec![*self as f64]
+        }
+    }
+
+    impl NumericField for f64 {
+        fn numeric_values(&self) -> Vec<f64> {
+            vec![*self]
+        }
+    }
+
+    impl<T: NumericField> NumericField for Option<T> {
+        fn numeric_values(&self) -> Vec<f64> {
+            self.iter().flat_map(NumericField::numeric_values).collect()
+        }
+    }
+
+    impl<T: NumericField> NumericField for Vec<T> {
+        fn numeric_values(&self) -> Vec<f64> {
+            self.iter().flat_map(NumericField::numeric_values).collect()
+        }
+    }
+
+    #[cfg(feature = "validate_fields")]
+    pub fn validate_range<T: NumericField>(
+        field: &str,
+        value: &T,
+        min: Option<f64>,
+        max: Option<f64>,
+    ) -> LdtkResult<()> {
+        for value in value.numeric_values() {
+            let too_small = min.map_or(false, |min| value < min);
+            let too_big = max.map_or(false, |max| value > max);
+
+            if too_small || too_big {
+                return Err(crate::error::LdtkError::FieldOutOfRange {
+                    field: field.to_string(),
+                    value,
+                    min,
+                    max,
+                });
+            }
+        }
 
-            let color = bevy::render::color::Color::hex(&hex[1..])?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "validate_fields"))]
+    pub fn validate_range<T: NumericField>(
+        _field: &str,
+        _value: &T,
+        _min: Option<f64>,
+        _max: Option<f64>,
+    ) -> LdtkResult<()> {
+        Ok(())
+    }
+
+    /// Checks that the pixel position of `entity` lies within its grid cell
+    ///
+    /// The two disagree in stale data, for example after changing the grid size in the editor.
+    /// Without a grid size, like with a default `EntityContext`, nothing is checked.
+    #[cfg(feature = "validate_positions")]
+    pub fn validate_position(entity: &ldtk2::EntityInstance, grid_size: i64) -> LdtkResult<()> {
+        if grid_size <= 0 {
+            return Ok(());
+        }
 
-            let new_field = serde_json::to_value(color)?;
+        let consistent = entity
+            .px
+            .iter()
+            .zip(&entity.grid)
+            .all(|(px, grid)| px.div_euclid(grid_size) == *grid);
 
-            Ok(serde_json::from_value(new_field)?)
+        if consistent {
+            Ok(())
         } else {
-            Ok(serde_json::from_value(field.clone())?)
+            Err(crate::error::LdtkError::EntityPositionMismatch {
+                entity: entity.identifier.clone(),
+            })
+        }
+    }
+
+    #[cfg(not(feature = "validate_positions"))]
+    pub fn validate_position(_entity: &ldtk2::EntityInstance, _grid_size: i64) -> LdtkResult<()> {
+        Ok(())
+    }
+
+    /// Parses a field, falling back to reading booleans and numbers that were exported as strings
+    ///
+    /// With the `strict_fields` feature there is no fallback and such strings are an error.
+    pub fn parse_field<T: DeserializeOwned>(field: &serde_json::Value) -> LdtkResult<T> {
+        #[cfg(not(feature = "strict_fields"))]
+        fn coerce(value: &serde_json::Value) -> serde_json::Value {
+            match value {
+                serde_json::Value::String(text) => match text.trim() {
+                    "true" => true.into(),
+                    "false" => false.into(),
+                    text => serde_json::from_str::<serde_json::Number>(text)
+                        .map(serde_json::Value::Number)
+                        .unwrap_or_else(|_| value.clone()),
+                },
+                serde_json::Value::Array(values) => {
+                    serde_json::Value::Array(values.iter().map(coerce).collect())
+                }
+                other => other.clone(),
+            }
+        }
+
+        let parsed = serde_json::from_value(field.clone());
+        #[cfg(not(feature = "strict_fields"))]
+        let parsed =
+            parsed.or_else(|source| serde_json::from_value(coerce(field)).map_err(|_| source));
+
+        parsed.map_err(|source| LdtkError::InvalidFieldValue {
+            value: field.to_string(),
+            source,
+        })
+    }
+
+    /// Parses a field containing file paths, which are made relative to `project_dir`
+    ///
+    /// Empty paths are treated as `null`, and are left out of arrays.
+    pub fn parse_file_path_field<T: DeserializeOwned>(
+        field: &serde_json::Value,
+        project_dir: &str,
+    ) -> LdtkResult<T> {
+        fn convert(value: &serde_json::Value, project_dir: &str) -> serde_json::Value {
+            match value {
+                serde_json::Value::String(path) if path.is_empty() => serde_json::Value::Null,
+                serde_json::Value::String(path) => std::path::Path::new(project_dir)
+                    .join(path)
+                    .to_string_lossy()
+                    .into_owned()
+                    .into(),
+                serde_json::Value::Array(values) => values
+                    .iter()
+                    .map(|value| convert(value, project_dir))
+                    .filter(|value| !value.is_null())
+                    .collect(),
+                other => other.clone(),
+            }
+        }
+
+        Ok(serde_json::from_value(convert(field, project_dir))?)
+    }
+
+    /// A field value made up of colors, which may also be `null` or an array of them
+    pub trait ColorField: Sized {
+        fn from_color_field(field: &serde_json::Value) -> LdtkResult<Self>;
+    }
+
+    impl<C: crate::LdtkColor> ColorField for C {
+        fn from_color_field(field: &serde_json::Value) -> LdtkResult<Self> {
+            let hex: String = serde_json::from_value(field.clone())?;
+            let [r, g, b, a] = crate::parse_color(&hex)?.as_rgba_f32();
+
+            let to_u8 = |channel: f32| (channel * 255.0).round() as u8;
+
+            Ok(C::from_rgba8([to_u8(r), to_u8(g), to_u8(b), to_u8(a)]))
+        }
+    }
+
+    impl<T: ColorField> ColorField for Option<T> {
+        fn from_color_field(field: &serde_json::Value) -> LdtkResult<Self> {
+            match field {
+                serde_json::Value::Null => Ok(None),
+                value => Ok(Some(T::from_color_field(value)?)),
+            }
+        }
+    }
+
+    impl<T: ColorField> ColorField for Vec<T> {
+        fn from_color_field(field: &serde_json::Value) -> LdtkResult<Self> {
+            let values: Vec<serde_json::Value> = serde_json::from_value(field.clone())?;
+
+            values.iter().map(T::from_color_field).collect()
+        }
+    }
+
+    /// Parses a field containing `#RRGGBB` colors, which may also be `null` or an array of them
+    pub fn parse_color_field<T: ColorField>(field: &serde_json::Value) -> LdtkResult<T> {
+        T::from_color_field(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile_instance(f: i64) -> ldtk2::TileInstance {
+        serde_json::from_value(serde_json::json!({
+            "px": [16, 32],
+            "src": [0, 0],
+            "f": f,
+            "t": 0,
+            "d": [0],
+        }))
+        .unwrap()
+    }
+
+    fn tile_at(x: i32, y: i32, src_px: IVec2) -> Tile {
+        Tile {
+            flip_x: false,
+            flip_y: false,
+            position_px: IVec2::new(x, y),
+            src_px,
+            id: 0,
+            alpha: 1.0,
+        }
+    }
+
+    /// A visible 4x4 tile layer with a grid size of 2
+    fn tile_layer(tiles: Vec<Tile>) -> Layer<()> {
+        Layer {
+            dimensions_cell: IVec2::new(4, 4),
+            grid_size: 2,
+            opacity: 1.0,
+            total_offset_px: IVec2::new(0, -8),
+            definition_offset_px: IVec2::ZERO,
+            instance_offset_px: IVec2::ZERO,
+            visible: true,
+            order: 0,
+            seed: 0,
+            tileset_uid: Some(1),
+            layer_definition: 1,
+            special: SpecialValues::Tiles {
+                tileset: Some(1),
+                tiles,
+            },
+        }
+    }
+
+    fn tileset(padding: i64, spacing: i64) -> Tileset {
+        Tileset {
+            grid_size: 2,
+            ident: "Tiles".to_string(),
+            padding,
+            spacing,
+            dimensions_cell: IVec2::new(2, 2),
+            dimensions_px: IVec2::splat((2 * padding + 2 * 2 + spacing) as i32),
+            rel_path: "tiles.png".to_string(),
+            id: 1,
+            cached_pixel_data: None,
+        }
+    }
+
+    #[test]
+    fn tile_orientation_follows_flip_bits() {
+        let orientation = |f| {
+            Tile::load(&tile_instance(f), IVec2::new(64, 64))
+                .unwrap()
+                .orientation()
+        };
+
+        assert_eq!(orientation(0), TileFlip::None);
+        assert_eq!(orientation(1), TileFlip::X);
+        assert_eq!(orientation(2), TileFlip::Y);
+        assert_eq!(orientation(3), TileFlip::XY);
+        // Bits above the lowest two are ignored
+        assert_eq!(orientation(4 | 1), TileFlip::X);
+    }
+
+    #[test]
+    fn restore_row_wise_undoes_reverse_row_wise() {
+        let full = (0..12).collect::<Vec<_>>();
+        let reversed = reverse_row_wise(full.clone(), 4);
+        assert_eq!(reversed, vec![8, 9, 10, 11, 4, 5, 6, 7, 0, 1, 2, 3]);
+        assert_eq!(restore_row_wise(reversed, 4), full);
+
+        // Sparse tile lists rarely fill their last row
+        let partial = (0..10).collect::<Vec<_>>();
+        let reversed = reverse_row_wise(partial.clone(), 4);
+        assert_eq!(restore_row_wise(reversed, 4), partial);
+    }
+
+    #[test]
+    fn tile_mesh_data_has_a_quad_per_tile() {
+        let tiles = vec![
+            tile_at(0, 7, IVec2::ZERO),
+            tile_at(2, 7, IVec2::new(2, 0)),
+            tile_at(4, 1, IVec2::new(0, 2)),
+        ];
+        let layer = tile_layer(tiles);
+
+        let data = layer.tile_mesh_data(&tileset(0, 0), false).unwrap();
+        assert_eq!(data.positions.len(), 4 * 3);
+        assert_eq!(data.uvs.len(), 4 * 3);
+        assert_eq!(data.colors.len(), 4 * 3);
+        assert_eq!(data.indices.len(), 6 * 3);
+    }
+
+    #[test]
+    fn tilemap_tiles_count_cells_from_the_bottom_left() {
+        let mut flipped = tile_at(2, 1, IVec2::ZERO);
+        flipped.flip_y = true;
+        let layer = tile_layer(vec![tile_at(0, 7, IVec2::ZERO), flipped]);
+
+        let tiles = layer.tilemap_tiles(false).unwrap();
+        assert_eq!(tiles[0].position, ::bevy::math::UVec2::new(0, 3));
+        assert_eq!(tiles[1].position, ::bevy::math::UVec2::new(1, 0));
+        assert!(tiles[1].flip_y);
+    }
+
+    #[test]
+    fn atlas_index_matches_the_ldtk_id_on_padded_tilesets() {
+        let tileset = tileset(1, 1);
+
+        for id in 0..4 {
+            let cell = IVec2::new(id % 2, id / 2);
+            // One pixel of padding around the image and one of spacing between tiles
+            let src_px = IVec2::splat(1) + cell * 3;
+            let tile = Tile {
+                id: id as i64,
+                ..tile_at(0, 7, src_px)
+            };
+
+            assert_eq!(tile.src_cell(&tileset), cell);
+            assert_eq!(tile.atlas_index(&tileset), tile.id as usize);
         }
     }
+
+    #[test]
+    #[cfg(feature = "rasterize")]
+    fn rasterize_draws_flipped_tiles_from_the_top() {
+        // A 4x4 tileset where red grows to the right and green downwards
+        let tileset_pixels = (0..4)
+            .flat_map(|y| (0..4).flat_map(move |x| vec![x * 60, y * 60, 0, 255]))
+            .collect::<Vec<u8>>();
+        let mut tile = tile_at(0, 7, IVec2::new(2, 0));
+        tile.flip_x = true;
+        let mut layer = tile_layer(vec![tile]);
+
+        let output = layer
+            .rasterize(&tileset_pixels, &tileset(0, 0), false)
+            .unwrap();
+
+        let mut expected = vec![0; 8 * 8 * 4];
+        let mut set = |x: usize, y: usize, pixel: [u8; 4]| {
+            expected[(y * 8 + x) * 4..][..4].copy_from_slice(&pixel);
+        };
+        set(0, 0, [180, 0, 0, 255]);
+        set(1, 0, [120, 0, 0, 255]);
+        set(0, 1, [180, 60, 0, 255]);
+        set(1, 1, [120, 60, 0, 255]);
+        assert_eq!(output, expected);
+
+        layer.visible = false;
+        assert!(layer
+            .rasterize(&tileset_pixels, &tileset(0, 0), false)
+            .is_none());
+        assert!(layer
+            .rasterize(&tileset_pixels, &tileset(0, 0), true)
+            .is_some());
+    }
+
+    #[test]
+    fn cached_pixel_data_decodes_argb_nibbles() {
+        use bevy::render::color::Color;
+        use std::collections::HashMap;
+
+        let data = |opaque: &str, colors: &str| {
+            vec![
+                ("opaqueTiles".to_string(), Some(opaque.into())),
+                ("averageColors".to_string(), Some(colors.into())),
+            ]
+            .into_iter()
+            .collect::<HashMap<_, _>>()
+        };
+
+        let cached = CachedPixelData::load(&data("10", "f0f88000")).unwrap();
+        assert_eq!(cached.opaque_tiles, vec![true, false]);
+        assert_eq!(
+            cached.average_colors,
+            vec![
+                Color::rgba(0.0, 1.0, 8.0 / 15.0, 1.0),
+                Color::rgba(0.0, 0.0, 0.0, 8.0 / 15.0),
+            ]
+        );
+
+        // Digits that are not hex, or a missing key, leave the data out
+        assert!(CachedPixelData::load(&data("1", "f0zz")).is_none());
+        assert!(CachedPixelData::load(&HashMap::new()).is_none());
+    }
 }
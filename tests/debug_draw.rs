@@ -0,0 +1,28 @@
+#![cfg(feature = "debug_draw")]
+
+use bevy::render::color::Color;
+use bevy_spicy_ldtk::{prelude::*, DebugShape, LdtkDebugDraw};
+
+ldtk! {pub layer_offsets, "assets/layer_offsets.ldtk"}
+
+#[test]
+fn entity_shapes_follow_their_layer_offset() {
+    let project =
+        layer_offsets::Project::deserialize_ldtk_from_path(layer_offsets::FILEPATH).unwrap();
+    let level = &project.levels[0];
+    let layer = &level.layers.entities;
+    let player = &layer.as_entities().unwrap().all_player[0];
+
+    let shapes = project.debug_shapes(&LdtkDebugDraw::default());
+
+    // The layer outline and the pivot of the player share the offset layer origin
+    let origin = level.layer_origin_px(layer).as_f32();
+    assert!(shapes.iter().any(|shape| matches!(
+        shape,
+        DebugShape::Rect { min, color, .. } if *min == origin && *color == Color::GRAY
+    )));
+    assert!(shapes.contains(&DebugShape::Point {
+        position: player.world_position_px.as_f32(),
+        color: Color::RED,
+    }));
+}
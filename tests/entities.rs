@@ -0,0 +1,130 @@
+use bevy::math::{IVec2, Vec2};
+use bevy_spicy_ldtk::{prelude::*, EntityIndex};
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+
+fn load() -> levels::Project {
+    levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap()
+}
+
+#[test]
+fn entity_grid_groups_entities_by_cell() {
+    let project = load();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+    let grid = entities.entity_grid();
+
+    assert_eq!(
+        grid.at(IVec2::new(3, 5)),
+        &[EntityIndex {
+            identifier: "Player",
+            index: 0,
+        }]
+    );
+    assert!(grid.at(IVec2::new(0, 0)).is_empty());
+    assert_eq!(grid.iter().count(), 3);
+}
+
+#[test]
+fn corner_and_center_helpers_follow_the_pivot() {
+    let project = load();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+    // Placed at (56, 176) in LDTK, 16x16 and anchored at the bottom center
+    let player = &entities.all_player[0];
+
+    assert_eq!(player.dimensions_px, IVec2::new(16, 16));
+    assert_eq!(player.pivot, Vec2::new(0.5, 0.0));
+    assert_eq!(player.position_px, IVec2::new(56, 79));
+    // It covers the pixel rows 80 to 95 of the 256 pixel layer
+    assert_eq!(player.bottom_left_px(), Vec2::new(48.0, 80.0));
+    assert_eq!(player.top_left_px(), Vec2::new(48.0, 96.0));
+    assert_eq!(player.center_px(), Vec2::new(56.0, 88.0));
+    assert!(!player.is_resized());
+}
+
+mod without_entities {
+    use bevy_spicy_ldtk::prelude::*;
+
+    ldtk! {pub levels, "assets/levels.ldtk", skip_entities(["Player", "SprayCan", "Fertilizer"])}
+
+    #[test]
+    fn projects_without_entities_are_generated() {
+        let project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+
+        let entities = project.levels[0].layers.entities().next().unwrap();
+        assert!(entities.entity_grid().iter().next().is_none());
+    }
+}
+
+mod stacked_layers {
+    use bevy_spicy_ldtk::prelude::*;
+
+    ldtk! {pub stacked, "assets/stacked_entity_layers.ldtk"}
+
+    #[test]
+    fn entities_of_higher_layers_are_drawn_on_top() {
+        let project = stacked::Project::deserialize_ldtk_from_path(stacked::FILEPATH).unwrap();
+        let layers = &project.levels[0].layers;
+
+        // Foreground is the top-most layer, Entities sits below Decoration
+        let spray_can = &layers.foreground.as_entities().unwrap().all_spray_can[0];
+        let player = &layers.entities.as_entities().unwrap().all_player[0];
+        assert_eq!(spray_can.layer_order, 4);
+        assert_eq!(player.layer_order, 2);
+
+        assert!(spray_can.world_transform().translation.z > player.world_transform().translation.z);
+    }
+}
+
+mod offset_layers {
+    use bevy::math::IVec2;
+    use bevy_spicy_ldtk::prelude::*;
+
+    ldtk! {pub layer_offsets, "assets/layer_offsets.ldtk"}
+
+    #[test]
+    fn world_positions_include_the_layer_offset() {
+        let project =
+            layer_offsets::Project::deserialize_ldtk_from_path(layer_offsets::FILEPATH).unwrap();
+
+        // StartLevel shifts its entity layer by (6, 2), Level_1 by (4, 8), both y down
+        let level = &project.levels[0];
+        let layer = &level.layers.entities;
+        let player = &layer.as_entities().unwrap().all_player[0];
+        assert_eq!(
+            player.world_position_px,
+            level.layer_origin_px(layer) + player.position_px
+        );
+        assert_eq!(player.world_position_px, IVec2::new(62, -179));
+
+        let level = &project.levels[1];
+        let spray_can = &level.layers.entities.as_entities().unwrap().all_spray_can[0];
+        assert_eq!(spray_can.world_position_px, IVec2::new(492, -73));
+    }
+}
+
+#[test]
+fn instances_of_other_entities_are_rejected() {
+    use bevy_spicy_ldtk::{error::LdtkError, private::ldtk2::EntityInstance, EntityContext};
+    use std::convert::TryFrom;
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(levels::FILEPATH).unwrap()).unwrap();
+    let spray_can: EntityInstance = serde_json::from_value(
+        json["levels"][0]["layerInstances"][1]["entityInstances"][1].clone(),
+    )
+    .unwrap();
+    assert_eq!(spray_can.identifier, "SprayCan");
+
+    let is_unknown_spray_can = |result: Result<levels::Player, LdtkError>| match result {
+        Err(LdtkError::InvalidEntity { identifier, source }) if identifier == "SprayCan" => {
+            matches!(*source, LdtkError::UnknownEntityType(ref unknown) if unknown == "SprayCan")
+        }
+        _ => false,
+    };
+
+    assert!(is_unknown_spray_can(levels::Player::from_instance(
+        &spray_can,
+        &EntityContext::default()
+    )));
+    assert!(is_unknown_spray_can(levels::Player::try_from(&spray_can)));
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use bevy_spicy_ldtk::prelude::*;
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+ldtk! {pub empty_enum, "assets/empty_enum.ldtk"}
+
+#[test]
+fn enums_can_be_used_as_map_keys() {
+    use levels::enums::FertilizerStrength;
+
+    let mut growth = HashMap::new();
+    growth.insert(FertilizerStrength::Weak, 1);
+    growth.insert(FertilizerStrength::Strong, 3);
+    growth.insert(FertilizerStrength::Weak, 2);
+
+    assert_eq!(growth.len(), 2);
+    assert_eq!(growth[&FertilizerStrength::Weak], 2);
+    assert_eq!(growth.get(&FertilizerStrength::Normal), None);
+}
+
+#[test]
+fn all_lists_every_value() {
+    use levels::enums::FertilizerStrength;
+
+    assert_eq!(FertilizerStrength::ALL.len(), FertilizerStrength::COUNT);
+    assert_eq!(
+        FertilizerStrength::ALL,
+        &[
+            FertilizerStrength::Weak,
+            FertilizerStrength::Normal,
+            FertilizerStrength::Strong
+        ]
+    );
+}
+
+#[test]
+fn enums_without_values_are_generated() {
+    assert_eq!(empty_enum::enums::Unused::COUNT, 0);
+    assert!(empty_enum::enums::Unused::ALL.is_empty());
+
+    empty_enum::Project::deserialize_ldtk_from_path(empty_enum::FILEPATH).unwrap();
+}
@@ -0,0 +1,144 @@
+use bevy::render::color::Color;
+use bevy_spicy_ldtk::{error::LdtkError, prelude::*};
+
+ldtk! {pub optional_int, "assets/optional_int_fields.ldtk"}
+
+#[test]
+fn nullable_fields_are_none_when_null_or_missing() {
+    let project =
+        optional_int::Project::deserialize_ldtk_from_path(optional_int::FILEPATH).unwrap();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+
+    let points = entities
+        .all_chest
+        .iter()
+        .map(|chest| chest.fields.points)
+        .collect::<Vec<_>>();
+    assert_eq!(points, vec![None, Some(2), None]);
+}
+
+#[test]
+fn missing_fields_that_cannot_be_null_are_an_error() {
+    let result = <optional_int::ChestFields as DeserializeLdtkFields>::deserialize_ldtk(&[]);
+
+    assert!(matches!(result, Err(LdtkError::MissingFieldsForEntities)));
+}
+
+ldtk! {pub color_fields, "assets/color_fields.ldtk"}
+
+#[test]
+fn color_fields_are_parsed_from_hex() {
+    let project =
+        color_fields::Project::deserialize_ldtk_from_path(color_fields::FILEPATH).unwrap();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+    let lamp = &entities.all_lamp[0];
+
+    assert_eq!(lamp.fields.tint, Color::rgba_u8(0xFF, 0x80, 0x00, 0xFF));
+    assert_eq!(lamp.fields.shade, None);
+}
+
+ldtk! {pub string_encoded_fields, "assets/string_encoded_fields.ldtk"}
+
+#[test]
+#[cfg(not(feature = "strict_fields"))]
+fn string_encoded_ints_and_bools_are_coerced() {
+    let project =
+        string_encoded_fields::Project::deserialize_ldtk_from_path(string_encoded_fields::FILEPATH)
+            .unwrap();
+
+    for level in &project.levels {
+        let entities = level.layers.entities().next().unwrap();
+        let spray_can = &entities.all_spray_can[0];
+
+        // Written as "5" and "true" in the project
+        assert_eq!(spray_can.fields.health, 5);
+        assert!(spray_can.fields.active);
+    }
+}
+
+#[test]
+#[cfg(feature = "strict_fields")]
+fn string_encoded_ints_are_an_error_with_strict_fields() {
+    let result =
+        string_encoded_fields::Project::deserialize_ldtk_from_path(string_encoded_fields::FILEPATH);
+
+    assert!(matches!(result, Err(LdtkError::InvalidFieldValue { value, .. }) if value == "\"5\""));
+}
+
+ldtk! {pub nullable_enum_fields, "assets/nullable_enum_fields.ldtk"}
+
+#[test]
+fn unset_enum_fields_are_none() {
+    use nullable_enum_fields::enums::FertilizerStrength;
+
+    let project =
+        nullable_enum_fields::Project::deserialize_ldtk_from_path(nullable_enum_fields::FILEPATH)
+            .unwrap();
+    let boost = |level: usize| {
+        let entities = project.levels[level].layers.entities().next().unwrap();
+        entities.all_fertilizer[0].fields.boost
+    };
+
+    assert_eq!(boost(0), None);
+    assert_eq!(boost(1), Some(FertilizerStrength::Strong));
+}
+
+ldtk! {pub file_path_fields, "${CARGO_MANIFEST_DIR}/assets/file_path_fields.ldtk"}
+
+#[test]
+fn file_paths_are_joined_with_the_project_dir() {
+    use std::path::Path;
+
+    let project_dir = Path::new(file_path_fields::PROJECT_DIR);
+    assert_eq!(
+        project_dir,
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+    );
+
+    let project =
+        file_path_fields::Project::deserialize_ldtk_from_path(file_path_fields::FILEPATH).unwrap();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+
+    // The empty path in between is left out
+    assert_eq!(
+        entities.all_spray_can[0].fields.sounds,
+        vec![
+            project_dir.join("sounds/spray.ogg"),
+            project_dir.join("sounds/empty.ogg")
+        ]
+    );
+}
+
+ldtk! {pub int_array_fields, "assets/int_array_fields.ldtk"}
+
+#[test]
+fn int_arrays_follow_the_element_nullability() {
+    let project =
+        int_array_fields::Project::deserialize_ldtk_from_path(int_array_fields::FILEPATH).unwrap();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+    let fields = &entities.all_spray_can[0].fields;
+
+    let combo: &Vec<Option<i64>> = &fields.combo;
+    let charges: &Vec<i64> = &fields.charges;
+    assert_eq!(combo, &vec![Some(1), None, Some(3)]);
+    assert_eq!(charges, &vec![2, 4]);
+}
+
+ldtk! {pub multiline_dialogue, "assets/multiline_dialogue.ldtk"}
+
+#[test]
+fn multiline_arrays_keep_their_line_breaks() {
+    let project =
+        multiline_dialogue::Project::deserialize_ldtk_from_path(multiline_dialogue::FILEPATH)
+            .unwrap();
+    let entities = project.levels[0].layers.entities().next().unwrap();
+
+    let dialogue: &Vec<String> = &entities.all_npc[0].fields.dialogue;
+    assert_eq!(
+        dialogue,
+        &vec![
+            "Welcome to the garden!\nMind the weeds.".to_string(),
+            "The spray can is by the shed.\nIt has three charges.\n\nUse them wisely.".to_string(),
+        ]
+    );
+}
@@ -0,0 +1,153 @@
+use bevy::math::Vec2;
+use bevy_spicy_ldtk::{error::LdtkError, prelude::*};
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+ldtk! {pub tile_pivots, "assets/tile_pivots.ldtk"}
+
+#[test]
+fn the_top_layer_has_the_largest_order() {
+    let project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    let layers = &project.levels[0].layers;
+
+    // LDTK lists Decoration first and Ground last
+    assert_eq!(layers.decoration.order, 3);
+    assert_eq!(layers.entities.order, 2);
+    assert_eq!(layers.details.order, 1);
+    assert_eq!(layers.ground.order, 0);
+}
+
+#[test]
+fn tile_pivots_are_flipped_to_y_up() {
+    let project = tile_pivots::Project::deserialize_ldtk_from_path(tile_pivots::FILEPATH).unwrap();
+
+    // Decoration is centered, Details sits at the bottom left of its cell
+    assert_eq!(
+        project.layer_definitions[&30].tile_pivot,
+        Vec2::new(0.5, 0.5)
+    );
+    assert_eq!(
+        project.layer_definitions[&31].tile_pivot,
+        Vec2::new(0.0, 0.0)
+    );
+}
+
+ldtk! {pub truncated_int_grid, "assets/truncated_int_grid.ldtk"}
+
+#[test]
+fn truncated_int_grids_are_an_error() {
+    let result =
+        truncated_int_grid::Project::deserialize_ldtk_from_path(truncated_int_grid::FILEPATH);
+
+    assert!(matches!(
+        result,
+        Err(LdtkError::GridSizeMismatch {
+            layer,
+            expected: 256,
+            found: 248,
+        }) if layer == "IntGridExampleLayer"
+    ));
+}
+
+ldtk! {pub mismatched_grid_sizes, "assets/mismatched_grid_sizes.ldtk"}
+
+#[test]
+fn tiles_use_the_tileset_grid_for_uvs_and_the_layer_grid_for_placement() {
+    let project =
+        mismatched_grid_sizes::Project::deserialize_ldtk_from_path(mismatched_grid_sizes::FILEPATH)
+            .unwrap();
+    let layer = &project.levels[0].layers.decoration;
+    let tileset = &project.tilesets[&1];
+    assert_eq!(layer.grid_size, 32);
+    assert_eq!(tileset.grid_size, 16);
+
+    // The tile is cut from (64, 80) of the 128x128 tileset
+    let tile = layer.tiles().unwrap()[0];
+    assert_eq!(
+        tile.src_rect_in(tileset),
+        (Vec2::new(0.5, 0.625), Vec2::new(0.625, 0.75))
+    );
+
+    let mesh = layer.tile_mesh_data(tileset, false).unwrap();
+    let width = mesh.positions[1][0] - mesh.positions[0][0];
+    let height = mesh.positions[2][1] - mesh.positions[1][1];
+    assert_eq!((width, height), (32.0, 32.0));
+}
+
+ldtk! {pub layer_offsets, "assets/layer_offsets.ldtk"}
+
+#[test]
+fn layer_offsets_are_split_into_definition_and_instance() {
+    use bevy::math::IVec2;
+
+    let project =
+        layer_offsets::Project::deserialize_ldtk_from_path(layer_offsets::FILEPATH).unwrap();
+
+    // The definition moves the layer by (4, 8) and StartLevel by another (2, -6), y down
+    let layer = &project.levels[0].layers.entities;
+    assert_eq!(layer.definition_offset_px, IVec2::new(4, -8));
+    assert_eq!(layer.instance_offset_px, IVec2::new(2, 6));
+    // The total offset also includes the height of the layer
+    assert_eq!(layer.total_offset_px, IVec2::new(6, -2 - 256));
+
+    let layer = &project.levels[1].layers.entities;
+    assert_eq!(layer.definition_offset_px, IVec2::new(4, -8));
+    assert_eq!(layer.instance_offset_px, IVec2::ZERO);
+    assert_eq!(layer.total_offset_px, IVec2::new(4, -8 - 512));
+}
+
+fn load_asset(name: &str) -> bevy_spicy_ldtk::error::LdtkResult<levels::Project> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(name);
+
+    levels::Project::deserialize_ldtk_from_path(path)
+}
+
+#[test]
+fn renamed_layers_are_found_by_their_definition_uid() {
+    // The same project, with the Details layer renamed to Props in the editor
+    let project = load_asset("renamed_layer.ldtk").unwrap();
+    let original = load_asset("levels.ldtk").unwrap();
+
+    for (level, original) in project.levels.iter().zip(&original.levels) {
+        let details = &level.layers.details;
+        assert_eq!(details.layer_definition, 31);
+        assert_eq!(details.order, original.layers.details.order);
+        assert_eq!(details.tiles(), original.layers.details.tiles());
+    }
+}
+
+#[test]
+fn missing_layers_are_an_error() {
+    // StartLevel lacks its Details layer
+    let result = load_asset("missing_layer.ldtk");
+
+    assert!(matches!(result, Err(LdtkError::MissingLayer(layer)) if layer == "Details"));
+}
+
+#[test]
+fn int_grid_colors_cover_empty_unknown_and_outside_cells() {
+    use bevy::{math::IVec2, render::color::Color};
+
+    let mut project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    let ground = &project.levels[0].layers.ground;
+    let color_at =
+        |x, y, definition: &LayerDefinition| ground.int_grid_color_at(IVec2::new(x, y), definition);
+
+    let definition = &project.layer_definitions[&2];
+    assert_eq!(color_at(1, 4, definition), Color::hex("93138C").ok());
+    assert_eq!(color_at(4, 4, definition), Color::hex("FF0000").ok());
+    // Empty cells are transparent
+    assert_eq!(color_at(0, 0, definition), Some(Color::NONE));
+    // Cells outside of the 16x16 layer have no color
+    assert_eq!(color_at(16, 0, definition), None);
+    assert_eq!(color_at(0, 16, definition), None);
+    assert_eq!(color_at(-1, 0, definition), None);
+
+    // Without a definition for Lava its cells have no color either
+    let definition = project.layer_definitions.get_mut(&2).unwrap();
+    if let SpecialLayerDefinitions::IntGrid { value_definitions } = &mut definition.special {
+        value_definitions.retain(|def| def.value != 2);
+    }
+    assert_eq!(color_at(4, 4, definition), None);
+}
@@ -0,0 +1,140 @@
+use bevy_spicy_ldtk::{error::LdtkError, prelude::*};
+
+ldtk! {pub level_field_defaults, "assets/level_field_defaults.ldtk"}
+
+#[test]
+fn missing_level_fields_use_the_definition_default() {
+    let project =
+        level_field_defaults::Project::deserialize_ldtk_from_path(level_field_defaults::FILEPATH)
+            .unwrap();
+
+    let difficulties = project
+        .levels
+        .iter()
+        .map(|level| level.fields.difficulty)
+        .collect::<Vec<_>>();
+    // Set explicitly, left out and set to null
+    assert_eq!(difficulties, vec![7, 3, 3]);
+}
+
+ldtk! {pub mixed_levels, "assets/mixed_levels.ldtk"}
+
+#[test]
+fn inline_and_external_levels_load_together() {
+    let project =
+        mixed_levels::Project::deserialize_ldtk_from_path(mixed_levels::FILEPATH).unwrap();
+
+    let counts = project
+        .levels
+        .iter()
+        .map(|level| {
+            let entities = level.layers.entities().next().unwrap();
+            (
+                level.identifier.as_str(),
+                entities.all_player.len(),
+                entities.all_spray_can.len(),
+                entities.all_fertilizer.len(),
+            )
+        })
+        .collect::<Vec<_>>();
+    // StartLevel is stored inline, Level_1 in its own file
+    assert_eq!(counts, vec![("StartLevel", 1, 1, 1), ("Level_1", 0, 1, 1)]);
+}
+
+#[test]
+fn external_levels_need_their_file() {
+    let bytes = std::fs::read(mixed_levels::FILEPATH).unwrap();
+    let result = mixed_levels::Project::deserialize_ldtk_from_slice(&bytes);
+
+    assert!(matches!(
+        result,
+        Err(LdtkError::MissingExternalLevel(level)) if level == "Level_1"
+    ));
+}
+
+#[test]
+fn external_levels_can_be_provided_from_a_slice() {
+    use std::path::{Path, PathBuf};
+
+    let bytes = std::fs::read(mixed_levels::FILEPATH).unwrap();
+    let mut requested = vec![];
+    let project = mixed_levels::Project::deserialize_ldtk_from_slice_with(&bytes, |path| {
+        requested.push(path.to_path_buf());
+        Ok(std::fs::read(
+            Path::new(mixed_levels::PROJECT_DIR).join(path),
+        )?)
+    })
+    .unwrap();
+
+    assert_eq!(
+        requested,
+        vec![PathBuf::from("mixed_levels/0001-Level_1.ldtkl")]
+    );
+    let entities = project.levels[1].layers.entities().next().unwrap();
+    assert_eq!(entities.all_spray_can.len(), 1);
+}
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+
+#[test]
+fn level_at_respects_the_boundaries_of_adjacent_levels() {
+    use bevy::math::IVec2;
+
+    let project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    let level_at = |x, y| {
+        project
+            .level_at(IVec2::new(x, y))
+            .map(|level| level.identifier.as_str())
+    };
+
+    // StartLevel covers x 0..256 and y -256..0, Level_1 starts right of it at x 256
+    assert_eq!(level_at(0, -256), Some("StartLevel"));
+    assert_eq!(level_at(255, -1), Some("StartLevel"));
+    assert_eq!(level_at(256, -1), Some("Level_1"));
+    assert_eq!(level_at(256, 255), Some("Level_1"));
+    assert_eq!(level_at(0, 0), None);
+    assert_eq!(level_at(-1, -1), None);
+    assert_eq!(level_at(768, 0), None);
+}
+
+ldtk! {pub unset_world_positions, "assets/unset_world_positions.ldtk"}
+
+#[test]
+fn levels_without_a_world_position_are_placed_at_the_origin() {
+    use bevy::math::IVec2;
+
+    let project =
+        unset_world_positions::Project::deserialize_ldtk_from_path(unset_world_positions::FILEPATH)
+            .unwrap();
+
+    // The top left corner is at the origin, positions are y up
+    assert_eq!(project.levels[0].world_position_px, IVec2::new(0, -256));
+    assert_eq!(project.levels[1].world_position_px, IVec2::new(0, -512));
+}
+
+#[test]
+fn world_grid_positions_are_the_gridvania_cells_with_y_up() {
+    use bevy::math::IVec2;
+
+    let project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    let cells = project
+        .levels
+        .iter()
+        .map(|level| level.world_grid_position)
+        .collect::<Vec<_>>();
+
+    // LDTK shows StartLevel in the cell (0, 0) and Level_1 in (1, -1) of the 256x256 grid
+    assert_eq!(cells, vec![Some(IVec2::new(0, 0)), Some(IVec2::new(1, 1))]);
+}
+
+#[test]
+fn levels_without_their_own_background_use_the_project_default() {
+    use bevy::render::color::Color;
+
+    let project = levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+
+    // Neither level sets a color, the project default is #283540
+    for level in &project.levels {
+        assert_eq!(level.background_color, Color::hex("283540").unwrap());
+    }
+}
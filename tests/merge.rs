@@ -0,0 +1,109 @@
+use bevy_spicy_ldtk::{error::LdtkError, prelude::*};
+use serde_json::Value;
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+
+fn load() -> levels::Project {
+    levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap()
+}
+
+/// Loads levels.ldtk after editing its JSON, to get a second world of the same type
+fn load_edited(edit: impl FnOnce(&mut Value)) -> levels::Project {
+    let mut json: Value =
+        serde_json::from_slice(&std::fs::read(levels::FILEPATH).unwrap()).unwrap();
+    edit(&mut json);
+
+    levels::Project::deserialize_ldtk_from_slice(&serde_json::to_vec(&json).unwrap()).unwrap()
+}
+
+fn shift_uids(items: &mut Value) {
+    for item in items.as_array_mut().unwrap() {
+        item["uid"] = (item["uid"].as_i64().unwrap() + 100).into();
+    }
+}
+
+fn rename_tilesets(json: &mut Value) {
+    for tileset in json["defs"]["tilesets"].as_array_mut().unwrap() {
+        tileset["identifier"] = format!("{}2", tileset["identifier"].as_str().unwrap()).into();
+    }
+}
+
+#[test]
+fn distinct_worlds_are_merged() {
+    let other = load_edited(|json| {
+        shift_uids(&mut json["levels"]);
+        shift_uids(&mut json["defs"]["tilesets"]);
+        shift_uids(&mut json["defs"]["layers"]);
+        rename_tilesets(json);
+    });
+
+    let merged = load().merge(other).unwrap();
+
+    assert_eq!(merged.levels.len(), 4);
+    assert_eq!(merged.tilesets.len(), 4);
+    assert_eq!(merged.layer_definitions.len(), 8);
+    assert_eq!(merged.tileset_by_identifier("World").unwrap().id, 1);
+    assert_eq!(merged.tileset_by_identifier("World2").unwrap().id, 101);
+    assert_eq!(merged.entity_count(), 2 * load().entity_count());
+}
+
+#[test]
+fn shared_level_uids_are_an_error() {
+    let result = load().merge(load());
+
+    assert!(matches!(
+        result,
+        Err(LdtkError::UidCollision {
+            kind: "level",
+            uid: 0
+        })
+    ));
+}
+
+#[test]
+fn shared_tileset_uids_are_an_error() {
+    let other = load_edited(|json| shift_uids(&mut json["levels"]));
+
+    assert!(matches!(
+        load().merge(other),
+        Err(LdtkError::UidCollision {
+            kind: "tileset",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn shared_layer_definition_uids_are_an_error() {
+    let other = load_edited(|json| {
+        shift_uids(&mut json["levels"]);
+        shift_uids(&mut json["defs"]["tilesets"]);
+        rename_tilesets(json);
+    });
+
+    assert!(matches!(
+        load().merge(other),
+        Err(LdtkError::UidCollision {
+            kind: "layer definition",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn shared_tileset_identifiers_are_an_error() {
+    // Every uid is distinct, but both worlds name a tileset "World"
+    let other = load_edited(|json| {
+        shift_uids(&mut json["levels"]);
+        shift_uids(&mut json["defs"]["tilesets"]);
+        shift_uids(&mut json["defs"]["layers"]);
+    });
+
+    assert!(matches!(
+        load().merge(other),
+        Err(LdtkError::IdentifierCollision {
+            kind: "tileset",
+            identifier,
+        }) if identifier == "World" || identifier == "Entities"
+    ));
+}
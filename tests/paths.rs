@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use bevy_spicy_ldtk::prelude::*;
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+
+#[test]
+fn filepath_is_resolved_against_the_crate_root() {
+    let path = Path::new(levels::FILEPATH);
+
+    assert!(path.is_absolute());
+    assert_eq!(
+        path,
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/levels.ldtk")
+    );
+
+    levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+}
+
+mod interpolated {
+    use std::path::Path;
+
+    use bevy_spicy_ldtk::prelude::*;
+
+    ldtk! {pub levels, "${CARGO_MANIFEST_DIR}/assets/levels.ldtk"}
+
+    #[test]
+    fn filepath_has_variables_interpolated() {
+        assert!(!levels::FILEPATH.contains('$'));
+        assert_eq!(
+            Path::new(levels::FILEPATH),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/levels.ldtk")
+        );
+
+        levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    }
+}
+
+mod from_env {
+    use std::path::Path;
+
+    use bevy_spicy_ldtk::prelude::*;
+
+    // Set to the absolute path of assets/levels.ldtk in .cargo/config.toml
+    ldtk! {pub levels, env!("LEVELS_LDTK")}
+
+    #[test]
+    fn filepath_is_read_from_the_variable() {
+        assert_eq!(
+            Path::new(levels::FILEPATH),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/levels.ldtk")
+        );
+
+        levels::Project::deserialize_ldtk_from_path(levels::FILEPATH).unwrap();
+    }
+}
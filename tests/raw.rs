@@ -0,0 +1,17 @@
+#![cfg(feature = "keep_raw")]
+
+use bevy_spicy_ldtk::prelude::*;
+
+ldtk! {pub levels, "assets/levels.ldtk"}
+
+#[test]
+fn raw_keeps_keys_unknown_to_ldtk2() {
+    let mut json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(levels::FILEPATH).unwrap()).unwrap();
+    json["customKey"] = serde_json::json!({ "kept": true });
+    let bytes = serde_json::to_vec(&json).unwrap();
+
+    let project = levels::Project::deserialize_ldtk_from_slice(&bytes).unwrap();
+
+    assert_eq!(project.raw(), &json);
+}
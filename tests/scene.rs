@@ -0,0 +1,24 @@
+#![cfg(feature = "serialize")]
+
+use bevy_spicy_ldtk::prelude::*;
+
+ldtk! {pub layer_offsets, "assets/layer_offsets.ldtk"}
+
+#[test]
+fn scene_entities_are_placed_within_their_layer() {
+    let project =
+        layer_offsets::Project::deserialize_ldtk_from_path(layer_offsets::FILEPATH).unwrap();
+    let scene = project.to_scene();
+
+    // The entity layer of StartLevel is shifted by (6, 2), y down
+    let layer = scene.levels[0]
+        .layers
+        .iter()
+        .find(|layer| !layer.entities.is_empty())
+        .unwrap();
+    assert_eq!(layer.position_px, [6, -258]);
+
+    let player = &layer.entities[0];
+    assert_eq!(player.identifier, "Player");
+    assert_eq!(player.position_px, [62, -179]);
+}
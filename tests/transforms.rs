@@ -0,0 +1,25 @@
+use bevy_spicy_ldtk::prelude::*;
+
+ldtk! {pub scaled, "assets/levels.ldtk", pixels_per_unit(16)}
+
+#[test]
+fn entities_and_tiles_share_the_scale() {
+    assert_eq!(scaled::PIXELS_PER_UNIT, 16.0);
+
+    let project = scaled::Project::deserialize_ldtk_from_path(scaled::FILEPATH).unwrap();
+    let level = &project.levels[0];
+
+    let player = &level.layers.entities().next().unwrap().all_player[0];
+    let transform = player.world_transform();
+    assert_eq!(
+        transform.translation.truncate(),
+        player.world_position_px.as_f32() / 16.0
+    );
+
+    let tile = level.layers.decoration.tiles().unwrap()[0];
+    let transform = scaled::tile_transform(&tile);
+    assert_eq!(
+        transform.translation.truncate(),
+        tile.position_px.as_f32() / 16.0
+    );
+}